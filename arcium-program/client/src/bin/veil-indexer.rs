@@ -0,0 +1,546 @@
+// veil-indexer - tails Veil program transactions and stores decoded events in SQLite
+// Polls getSignaturesForAddress from the last indexed signature, so restarts resume cleanly
+
+use std::path::PathBuf;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::Parser;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+
+use veil_client::events::{parse_logs, VeilEvent};
+use veil_client::pda::VEIL_PROGRAM_ID;
+use veil_client::rpc::{RpcClient, TransactionLogs, DEFAULT_RPC_URL};
+use veil_client::state::RULE_PRIVATE_RESULTS;
+
+const PAGE_SIZE: usize = 1000;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS games (
+    authority      TEXT NOT NULL,
+    game_id        INTEGER NOT NULL, -- Per authority
+    total_players  INTEGER NOT NULL,
+    votes_received INTEGER NOT NULL DEFAULT 0,
+    finished       INTEGER NOT NULL DEFAULT 0,
+    community_won  INTEGER,
+    closed         INTEGER NOT NULL DEFAULT 0,
+    first_slot     INTEGER NOT NULL,
+    last_slot      INTEGER NOT NULL,
+    PRIMARY KEY (authority, game_id)
+);
+CREATE TABLE IF NOT EXISTS votes (
+    signature      TEXT NOT NULL,
+    log_index      INTEGER NOT NULL,
+    authority      TEXT NOT NULL,
+    game_id        INTEGER NOT NULL,
+    votes_received INTEGER NOT NULL,
+    total_players  INTEGER NOT NULL,
+    slot           INTEGER NOT NULL,
+    block_time     INTEGER,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE INDEX IF NOT EXISTS votes_game ON votes (authority, game_id);
+CREATE TABLE IF NOT EXISTS results (
+    signature            TEXT NOT NULL,
+    log_index            INTEGER NOT NULL,
+    authority            TEXT NOT NULL,
+    game_id              INTEGER NOT NULL,
+    saboteur_index       INTEGER NOT NULL,
+    saboteur_voted_safe  INTEGER NOT NULL,
+    community_voted_safe INTEGER NOT NULL,
+    community_won        INTEGER NOT NULL,
+    player_results       TEXT NOT NULL, -- JSON array of bools, by seat; empty if private
+    rules                INTEGER NOT NULL, -- RULE_* bitmask
+    slot                 INTEGER NOT NULL,
+    block_time           INTEGER,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE INDEX IF NOT EXISTS results_game ON results (authority, game_id);
+CREATE TABLE IF NOT EXISTS lifecycle (
+    signature          TEXT NOT NULL,
+    log_index          INTEGER NOT NULL,
+    event              TEXT NOT NULL,
+    game_id            INTEGER NOT NULL,
+    authority          TEXT NOT NULL,
+    kind               INTEGER, -- ComputationKind, for computation events
+    computation_offset INTEGER,
+    slot               INTEGER NOT NULL, -- Slot reported by the program
+    block_time         INTEGER,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE TABLE IF NOT EXISTS fees (
+    signature      TEXT NOT NULL,
+    log_index      INTEGER NOT NULL,
+    game_id        INTEGER NOT NULL,
+    authority      TEXT NOT NULL,
+    kind           INTEGER NOT NULL, -- FeeKind
+    amount         INTEGER NOT NULL, -- Lamports
+    slot           INTEGER NOT NULL,
+    block_time     INTEGER,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE TABLE IF NOT EXISTS settlements (
+    signature      TEXT NOT NULL,
+    log_index      INTEGER NOT NULL,
+    game_id        INTEGER NOT NULL,
+    authority      TEXT NOT NULL,
+    pot            INTEGER NOT NULL,
+    protocol_fee   INTEGER NOT NULL,
+    payout         INTEGER NOT NULL, -- Per paid seat
+    paid_seats     INTEGER NOT NULL, -- Bitmask by seat
+    refunded       INTEGER NOT NULL,
+    slot           INTEGER NOT NULL,
+    block_time     INTEGER,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE TABLE IF NOT EXISTS withdrawals (
+    signature      TEXT NOT NULL,
+    log_index      INTEGER NOT NULL,
+    admin          TEXT NOT NULL,
+    recipient      TEXT NOT NULL,
+    amount         INTEGER NOT NULL,
+    slot           INTEGER NOT NULL,
+    block_time     INTEGER,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE TABLE IF NOT EXISTS ratings (
+    signature      TEXT NOT NULL,
+    log_index      INTEGER NOT NULL,
+    game_id        INTEGER NOT NULL,
+    player         TEXT NOT NULL,
+    seat           INTEGER NOT NULL,
+    was_saboteur   INTEGER NOT NULL,
+    old_rating     INTEGER NOT NULL,
+    new_rating     INTEGER NOT NULL,
+    slot           INTEGER NOT NULL,
+    block_time     INTEGER,
+    PRIMARY KEY (signature, log_index)
+);
+CREATE INDEX IF NOT EXISTS ratings_player ON ratings (player, slot);
+CREATE TABLE IF NOT EXISTS seasons (
+    season_id      INTEGER PRIMARY KEY,
+    entry_count    INTEGER NOT NULL,
+    winner         TEXT NOT NULL,
+    closed_slot    INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tournaments (
+    organizer         TEXT NOT NULL,
+    tournament_id     INTEGER NOT NULL,
+    round             INTEGER NOT NULL,
+    remaining_players INTEGER NOT NULL,
+    finished          INTEGER NOT NULL DEFAULT 0,
+    prize_pool        INTEGER, -- Set once prizes are distributed
+    payout            INTEGER, -- Per champion
+    last_slot         INTEGER NOT NULL,
+    PRIMARY KEY (organizer, tournament_id)
+);
+CREATE TABLE IF NOT EXISTS cursor (
+    id             INTEGER PRIMARY KEY CHECK (id = 1),
+    last_signature TEXT NOT NULL
+);
+";
+
+#[derive(Parser)]
+#[command(name = "veil-indexer", about = "Index Veil game events into SQLite")]
+struct Cli {
+    /// Solana JSON-RPC endpoint
+    #[arg(long, default_value = DEFAULT_RPC_URL)]
+    url: String,
+    /// SQLite database file
+    #[arg(long, default_value = "veil.db")]
+    db: PathBuf,
+    /// Delay between polls in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    poll_interval_ms: u64,
+    /// Index what is available and exit
+    #[arg(long)]
+    once: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new(&cli.url);
+    let mut conn = Connection::open(&cli.db)?;
+    conn.execute_batch(SCHEMA)?;
+
+    println!("Indexing {} from {} into {}", VEIL_PROGRAM_ID, cli.url, cli.db.display());
+
+    loop {
+        match poll(&rpc, &mut conn) {
+            Ok(0) => {}
+            Ok(n) => println!("Indexed {} transactions", n),
+            Err(e) => eprintln!("Poll failed: {:#}", e),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        sleep(Duration::from_millis(cli.poll_interval_ms));
+    }
+}
+
+/// Indexes every transaction since the cursor, oldest first; returns how many were processed
+fn poll(rpc: &RpcClient, conn: &mut Connection) -> Result<usize> {
+    let cursor: Option<String> = conn
+        .query_row("SELECT last_signature FROM cursor WHERE id = 1", [], |row| row.get(0))
+        .optional()?;
+
+    // Pages come newest first; walk back with `before` until reaching the cursor
+    let mut pending = Vec::new();
+    let mut before: Option<String> = None;
+    loop {
+        let page = rpc.signatures_for_address(
+            &VEIL_PROGRAM_ID,
+            cursor.as_deref(),
+            before.as_deref(),
+            PAGE_SIZE,
+        )?;
+        let done = page.len() < PAGE_SIZE;
+        before = page.last().map(|info| info.signature.clone());
+        pending.extend(page);
+        if done || before.is_none() {
+            break;
+        }
+    }
+
+    let count = pending.len();
+    for info in pending.into_iter().rev() {
+        let logs = if info.failed {
+            None
+        } else {
+            rpc.transaction_logs(&info.signature)?
+        };
+
+        let tx = conn.transaction()?;
+        if let Some(logs) = logs {
+            record(&tx, &info.signature, &logs)?;
+        }
+        tx.execute(
+            "INSERT INTO cursor (id, last_signature) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET last_signature = excluded.last_signature",
+            params![info.signature],
+        )?;
+        tx.commit()?;
+    }
+
+    Ok(count)
+}
+
+fn record(tx: &Transaction, signature: &str, logs: &TransactionLogs) -> Result<()> {
+    let slot = logs.slot as i64;
+
+    for (log_index, event) in parse_logs(&logs.logs).into_iter().enumerate() {
+        let log_index = log_index as i64;
+        let name = event.name();
+
+        match event {
+            VeilEvent::VoteSubmitted(e) => {
+                let authority = e.authority.to_string();
+                tx.execute(
+                    "INSERT OR IGNORE INTO votes
+                     (signature, log_index, authority, game_id, votes_received, total_players,
+                      slot, block_time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        signature,
+                        log_index,
+                        authority,
+                        e.game_id,
+                        e.votes_received,
+                        e.total_players,
+                        slot,
+                        logs.block_time
+                    ],
+                )?;
+                tx.execute(
+                    "INSERT INTO games
+                     (authority, game_id, total_players, votes_received, first_slot, last_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+                     ON CONFLICT(authority, game_id) DO UPDATE SET
+                         total_players = excluded.total_players,
+                         votes_received = MAX(votes_received, excluded.votes_received),
+                         last_slot = excluded.last_slot",
+                    params![authority, e.game_id, e.total_players, e.votes_received, slot],
+                )?;
+            }
+            VeilEvent::GameResult(e) => {
+                let authority = e.authority.to_string();
+                tx.execute(
+                    "INSERT OR IGNORE INTO results
+                     (signature, log_index, authority, game_id, saboteur_index, saboteur_voted_safe,
+                      community_voted_safe, community_won, player_results, rules, slot, block_time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                    params![
+                        signature,
+                        log_index,
+                        authority,
+                        e.game_id,
+                        e.saboteur_index,
+                        e.saboteur_voted_safe,
+                        e.community_voted_safe,
+                        e.community_won,
+                        serde_json::to_string(&e.player_results)?,
+                        e.rules,
+                        slot,
+                        logs.block_time
+                    ],
+                )?;
+                // Private games publish no per-seat results, but seal one to every seat
+                let total_players = if e.rules & RULE_PRIVATE_RESULTS != 0 {
+                    e.sealed_results.len()
+                } else {
+                    e.player_results.len()
+                };
+                tx.execute(
+                    "INSERT INTO games
+                     (authority, game_id, total_players, finished, community_won, first_slot, last_slot)
+                     VALUES (?1, ?2, ?3, 1, ?4, ?5, ?5)
+                     ON CONFLICT(authority, game_id) DO UPDATE SET
+                         finished = 1,
+                         community_won = excluded.community_won,
+                         last_slot = excluded.last_slot",
+                    params![authority, e.game_id, total_players, e.community_won, slot],
+                )?;
+            }
+            VeilEvent::GameCreated(e) => {
+                let authority = e.authority.to_string();
+                let lifecycle = Lifecycle::new(e.game_id, &authority, e.slot);
+                lifecycle.insert(tx, signature, log_index, name, logs.block_time)?;
+                tx.execute(
+                    "INSERT INTO games (authority, game_id, total_players, first_slot, last_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?4)
+                     ON CONFLICT(authority, game_id) DO UPDATE SET
+                         total_players = excluded.total_players,
+                         last_slot = excluded.last_slot",
+                    params![authority, e.game_id, e.num_players, slot],
+                )?;
+            }
+            VeilEvent::VotingOpened(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle::new(e.game_id, &authority, e.slot)
+                    .insert(tx, signature, log_index, name, logs.block_time)?;
+            }
+            VeilEvent::ComputationQueued(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle {
+                    kind: Some(e.kind),
+                    computation_offset: Some(e.computation_offset as i64),
+                    ..Lifecycle::new(e.game_id, &authority, e.slot)
+                }
+                .insert(tx, signature, log_index, name, logs.block_time)?;
+            }
+            VeilEvent::ComputationAborted(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle {
+                    kind: Some(e.kind),
+                    ..Lifecycle::new(e.game_id, &authority, e.slot)
+                }
+                .insert(tx, signature, log_index, name, logs.block_time)?;
+            }
+            VeilEvent::GameClosed(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle::new(e.game_id, &authority, e.slot)
+                    .insert(tx, signature, log_index, name, logs.block_time)?;
+                tx.execute(
+                    "UPDATE games SET closed = 1, last_slot = ?3 WHERE authority = ?1 AND game_id = ?2",
+                    params![authority, e.game_id, slot],
+                )?;
+            }
+            VeilEvent::MatchFormed(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle::new(e.game_id, &authority, e.slot)
+                    .insert(tx, signature, log_index, name, logs.block_time)?;
+            }
+            VeilEvent::SaboteurDisclosed(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle::new(e.game_id, &authority, e.slot)
+                    .insert(tx, signature, log_index, name, logs.block_time)?;
+            }
+            VeilEvent::GameFrozen(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle::new(e.game_id, &authority, e.slot)
+                    .insert(tx, signature, log_index, name, logs.block_time)?;
+            }
+            VeilEvent::GameUnfrozen(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle::new(e.game_id, &authority, e.slot)
+                    .insert(tx, signature, log_index, name, logs.block_time)?;
+            }
+            VeilEvent::FeeCollected(e) => {
+                tx.execute(
+                    "INSERT OR IGNORE INTO fees
+                     (signature, log_index, game_id, authority, kind, amount, slot, block_time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        signature,
+                        log_index,
+                        e.game_id,
+                        e.authority.to_string(),
+                        e.kind,
+                        e.amount as i64,
+                        e.slot as i64,
+                        logs.block_time
+                    ],
+                )?;
+            }
+            VeilEvent::GameSettled(e) => {
+                let authority = e.authority.to_string();
+                Lifecycle::new(e.game_id, &authority, e.slot)
+                    .insert(tx, signature, log_index, name, logs.block_time)?;
+                tx.execute(
+                    "INSERT OR IGNORE INTO settlements
+                     (signature, log_index, game_id, authority, pot, protocol_fee, payout,
+                      paid_seats, refunded, slot, block_time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                    params![
+                        signature,
+                        log_index,
+                        e.game_id,
+                        authority,
+                        e.pot as i64,
+                        e.protocol_fee as i64,
+                        e.payout as i64,
+                        e.paid_seats,
+                        e.refunded,
+                        e.slot as i64,
+                        logs.block_time
+                    ],
+                )?;
+            }
+            VeilEvent::RatingUpdated(e) => {
+                tx.execute(
+                    "INSERT OR IGNORE INTO ratings
+                     (signature, log_index, game_id, player, seat, was_saboteur,
+                      old_rating, new_rating, slot, block_time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    params![
+                        signature,
+                        log_index,
+                        e.game_id,
+                        e.player.to_string(),
+                        e.seat,
+                        e.was_saboteur,
+                        e.old_rating,
+                        e.new_rating,
+                        e.slot as i64,
+                        logs.block_time
+                    ],
+                )?;
+            }
+            VeilEvent::SeasonClosed(e) => {
+                tx.execute(
+                    "INSERT OR REPLACE INTO seasons (season_id, entry_count, winner, closed_slot)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![e.season_id, e.entry_count, e.winner.to_string(), e.slot as i64],
+                )?;
+            }
+            VeilEvent::TournamentAdvanced(e) => {
+                tx.execute(
+                    "INSERT INTO tournaments
+                     (organizer, tournament_id, round, remaining_players, finished, last_slot)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                     ON CONFLICT(organizer, tournament_id) DO UPDATE SET
+                         round = excluded.round,
+                         remaining_players = excluded.remaining_players,
+                         finished = excluded.finished,
+                         last_slot = excluded.last_slot",
+                    params![
+                        e.organizer.to_string(),
+                        e.tournament_id,
+                        e.round,
+                        e.remaining_players,
+                        e.finished,
+                        e.slot as i64
+                    ],
+                )?;
+            }
+            VeilEvent::TournamentPrizesDistributed(e) => {
+                tx.execute(
+                    "INSERT INTO tournaments
+                     (organizer, tournament_id, round, remaining_players, finished,
+                      prize_pool, payout, last_slot)
+                     VALUES (?1, ?2, 0, ?3, 1, ?4, ?5, ?6)
+                     ON CONFLICT(organizer, tournament_id) DO UPDATE SET
+                         finished = 1,
+                         prize_pool = excluded.prize_pool,
+                         payout = excluded.payout,
+                         last_slot = excluded.last_slot",
+                    params![
+                        e.organizer.to_string(),
+                        e.tournament_id,
+                        e.champions,
+                        e.prize_pool as i64,
+                        e.payout as i64,
+                        e.slot as i64
+                    ],
+                )?;
+            }
+            VeilEvent::TreasuryWithdrawn(e) => {
+                tx.execute(
+                    "INSERT OR IGNORE INTO withdrawals
+                     (signature, log_index, admin, recipient, amount, slot, block_time)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        signature,
+                        log_index,
+                        e.admin.to_string(),
+                        e.recipient.to_string(),
+                        e.amount as i64,
+                        e.slot as i64,
+                        logs.block_time
+                    ],
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Row of the lifecycle table
+struct Lifecycle<'a> {
+    game_id: u32,
+    authority: &'a str,
+    kind: Option<u8>,
+    computation_offset: Option<i64>,
+    slot: u64,
+}
+
+impl<'a> Lifecycle<'a> {
+    fn new(game_id: u32, authority: &'a str, slot: u64) -> Self {
+        Self {
+            game_id,
+            authority,
+            kind: None,
+            computation_offset: None,
+            slot,
+        }
+    }
+
+    fn insert(
+        &self,
+        tx: &Transaction,
+        signature: &str,
+        log_index: i64,
+        event: &str,
+        block_time: Option<i64>,
+    ) -> Result<()> {
+        tx.execute(
+            "INSERT OR IGNORE INTO lifecycle
+             (signature, log_index, event, game_id, authority, kind, computation_offset, slot, block_time)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                signature,
+                log_index,
+                event,
+                self.game_id,
+                self.authority,
+                self.kind,
+                self.computation_offset,
+                self.slot as i64,
+                block_time
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,1302 @@
+// veil - host, play and debug Veil games from the command line
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::json;
+use rand_core::{OsRng, RngCore};
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_signer::Signer;
+
+use veil_client::encryption::{
+    decrypt_result, decrypt_role, decrypt_saboteur, encrypt_vote_as, Ballot, VoteKeypair,
+};
+use veil_client::fairness::verify_saboteur;
+use veil_client::instructions::{self, ConfigParams, COMP_DEFS};
+use veil_client::pda::{self, VEIL_PROGRAM_ID};
+use veil_client::rpc::{RpcClient, DEFAULT_RPC_URL};
+use veil_client::state::{
+    account_discriminator, Config, GameAccount, HostState, MatchQueue, PlayerProfile, RoomIndex, Season, SeasonEntry,
+    GameStatus, SeasonResult, Tournament, Treasury, VoteAccount, RULE_HOST_DISCLOSURE, RULE_PRIVATE_RESULTS,
+    RULE_SECRET_SABOTEUR,
+};
+
+/// Season entries ranked per close_season transaction
+const CLOSE_SEASON_BATCH: usize = 20;
+
+#[derive(Parser)]
+#[command(name = "veil", about = "Host and play Veil games without the Node server")]
+struct Cli {
+    /// Solana JSON-RPC endpoint
+    #[arg(long, global = true, default_value = DEFAULT_RPC_URL)]
+    url: String,
+    /// Signer keypair file [default: ~/.config/solana/id.json]
+    #[arg(long, global = true)]
+    keypair: Option<PathBuf>,
+    /// Arcium cluster offset the MXE is bound to (needed by create, vote and reveal)
+    #[arg(long, global = true)]
+    cluster_offset: Option<u32>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Register the init_game and reveal_result computation definitions
+    InitCompDefs,
+    /// Create a game hosted by the signer; the program assigns the game id
+    Create {
+        #[arg(long)]
+        players: u8,
+        /// Room code (VEIL-XXXXXX) players can join by
+        #[arg(long)]
+        room: Option<String>,
+        /// Lamports each player stakes when joining
+        #[arg(long, default_value_t = 0)]
+        wager: u64,
+        /// Publish only who won; each player opens their own result with `veil result`
+        #[arg(long)]
+        private_results: bool,
+        /// Also keep the saboteur secret from everyone but the saboteur (implies --private-results)
+        #[arg(long)]
+        secret_saboteur: bool,
+        /// Let the host disclose the secret saboteur this many seconds after the reveal
+        #[arg(long, requires = "secret_saboteur")]
+        host_disclosure_delay: Option<i64>,
+    },
+    /// Claim a seat in a game
+    Join {
+        #[command(flatten)]
+        game: GameRef,
+        #[arg(long)]
+        seat: u8,
+    },
+    /// Encrypt and submit a vote for the signer's seat
+    Vote {
+        #[command(flatten)]
+        game: GameRef,
+        #[arg(long)]
+        seat: u8,
+        #[arg(long, value_enum)]
+        choice: Choice,
+        /// MXE x25519 public key, hex encoded
+        #[arg(long)]
+        mxe_key: String,
+    },
+    /// Print the ballot stored for a seat
+    Ballot {
+        #[command(flatten)]
+        game: GameRef,
+        #[arg(long)]
+        seat: u8,
+        /// Close the vote account of a finished game and reclaim its rent
+        #[arg(long)]
+        close: bool,
+    },
+    /// Decrypt the signer's result in a finished game with private results
+    Result {
+        #[command(flatten)]
+        game: GameRef,
+        #[arg(long)]
+        seat: u8,
+        /// MXE x25519 public key, hex encoded
+        #[arg(long)]
+        mxe_key: String,
+    },
+    /// Check that a revealed game's saboteur was fixed by the seed committed before voting
+    Verify {
+        #[command(flatten)]
+        game: GameRef,
+    },
+    /// Disclose the secret saboteur of a game hosted by the signer, or print it once disclosed
+    Disclose {
+        #[arg(long)]
+        game_id: u32,
+        /// MXE x25519 public key, hex encoded
+        #[arg(long)]
+        mxe_key: String,
+    },
+    /// Queue the reveal computation for a game; anyone may reveal matchmade games, and any
+    /// game an hour after every seat voted or the voting deadline passed
+    Reveal {
+        #[command(flatten)]
+        game: GameRef,
+        /// Season whose entries of the seat owners should score this game (repeatable)
+        #[arg(long = "season")]
+        seasons: Vec<u32>,
+    },
+    /// Re-queue a game's aborted init or reveal computation
+    Retry {
+        #[command(flatten)]
+        game: GameRef,
+        /// As for reveal
+        #[arg(long = "season")]
+        seasons: Vec<u32>,
+    },
+    /// List the games created by a host that are still open
+    Games {
+        /// Defaults to the signer
+        #[arg(long)]
+        host: Option<String>,
+    },
+    /// Print a game's on-chain state
+    Status {
+        #[command(flatten)]
+        game: GameRef,
+    },
+    /// Pay out a game's wager pot, or refund it if the game was frozen or failed
+    Settle {
+        #[command(flatten)]
+        game: GameRef,
+    },
+    /// Halt a disputed game (config admin only)
+    Freeze {
+        #[command(flatten)]
+        game: GameRef,
+    },
+    /// Resume a frozen game (config admin only)
+    Unfreeze {
+        #[command(flatten)]
+        game: GameRef,
+    },
+    /// Close a finished or frozen game hosted by the signer
+    Close {
+        #[arg(long)]
+        game_id: u32,
+    },
+    /// Create or inspect lifetime player stats
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommand,
+    },
+    /// Run and inspect season leaderboards
+    Season {
+        #[command(subcommand)]
+        command: SeasonCommand,
+    },
+    /// Run multi-round tournaments
+    Tournament {
+        #[command(subcommand)]
+        command: TournamentCommand,
+    },
+    /// Find games through the public matchmaking queue
+    Queue {
+        #[command(subcommand)]
+        command: QueueCommand,
+    },
+    /// Inspect or administer the program config
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommand {
+    /// Create the signer's profile so finished games count towards it
+    Create,
+    /// Print a profile
+    Show {
+        /// Defaults to the signer
+        #[arg(long)]
+        player: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SeasonCommand {
+    /// Create a season (config admin only)
+    Create {
+        #[arg(long)]
+        season_id: u32,
+        /// Unix timestamp
+        #[arg(long)]
+        start: i64,
+        /// Unix timestamp
+        #[arg(long)]
+        end: i64,
+    },
+    /// Enter the signer in a season
+    Join {
+        #[arg(long)]
+        season_id: u32,
+    },
+    /// Rank all entries of an ended season into its result
+    Close {
+        #[arg(long)]
+        season_id: u32,
+    },
+    /// Print a season and its standings
+    Show {
+        #[arg(long)]
+        season_id: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum TournamentCommand {
+    /// Open registration for a tournament organized by the signer
+    Create {
+        #[arg(long)]
+        tournament_id: u32,
+        /// Lamports each entrant pays into the prize pool
+        #[arg(long, default_value_t = 0)]
+        entry_fee: u64,
+        #[arg(long)]
+        max_entrants: u8,
+        /// Lamports the organizer adds to the prize pool
+        #[arg(long, default_value_t = 0)]
+        prize: u64,
+    },
+    /// Register the signer, paying the entry fee
+    Register {
+        #[command(flatten)]
+        tournament: TournamentRef,
+    },
+    /// Close registration and start round 1
+    Start {
+        #[arg(long)]
+        tournament_id: u32,
+    },
+    /// Seat the next unseated players into one of the signer's empty games
+    Seat {
+        #[arg(long)]
+        tournament_id: u32,
+        #[arg(long)]
+        game_id: u32,
+    },
+    /// Accept the signer's seat in a tournament game, required before voting
+    Confirm {
+        #[command(flatten)]
+        game: GameRef,
+        #[arg(long)]
+        seat: u8,
+    },
+    /// Move the winners of the finished round on
+    Advance {
+        #[command(flatten)]
+        tournament: TournamentRef,
+    },
+    /// Pay the prize pool of a finished tournament to its champions
+    Distribute {
+        #[command(flatten)]
+        tournament: TournamentRef,
+    },
+    /// Print a tournament
+    Show {
+        #[command(flatten)]
+        tournament: TournamentRef,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueCommand {
+    /// Wait in the queue, staking --stake lamports
+    Join {
+        #[command(flatten)]
+        queue: QueueRef,
+        /// Largest rating gap accepted to the other players, 0 = any
+        #[arg(long, default_value_t = 0)]
+        rating_band: u16,
+    },
+    /// Leave the queue and get the stake back
+    Leave {
+        #[command(flatten)]
+        queue: QueueRef,
+    },
+    /// Create a game from compatible queued players; the signer hosts it
+    Match {
+        #[command(flatten)]
+        queue: QueueRef,
+    },
+    /// Print the players waiting in a queue
+    Show {
+        #[command(flatten)]
+        queue: QueueRef,
+    },
+}
+
+#[derive(Args)]
+struct QueueRef {
+    #[arg(long)]
+    players: u8,
+    /// Lamports per player
+    #[arg(long, default_value_t = 0)]
+    stake: u64,
+}
+
+#[derive(Args)]
+struct TournamentRef {
+    /// Defaults to the signer
+    #[arg(long)]
+    organizer: Option<String>,
+    #[arg(long)]
+    tournament_id: u32,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the program config
+    Show,
+    /// Create the config; the signer must be the program upgrade authority and becomes admin
+    Init {
+        #[arg(long, default_value_t = 8)]
+        max_players: u8,
+        /// Default voting window in seconds, 0 = no deadline
+        #[arg(long, default_value_t = 0)]
+        voting_period: i64,
+        /// Lamports charged per game
+        #[arg(long, default_value_t = 0)]
+        creation_fee: u64,
+        #[arg(long, default_value_t = 0)]
+        protocol_fee_bps: u16,
+        /// Share of seats that must vote to reveal once the voting period ended
+        #[arg(long, default_value_t = 10_000)]
+        quorum_bps: u16,
+        /// Start with game creation and voting blocked
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Change config fields; omitted fields keep their current value
+    Update {
+        #[arg(long)]
+        max_players: Option<u8>,
+        #[arg(long)]
+        voting_period: Option<i64>,
+        #[arg(long)]
+        creation_fee: Option<u64>,
+        #[arg(long)]
+        protocol_fee_bps: Option<u16>,
+        #[arg(long)]
+        quorum_bps: Option<u16>,
+        #[arg(long)]
+        paused: Option<bool>,
+    },
+    /// Block new games and votes program-wide
+    Pause,
+    /// Lift a pause
+    Unpause,
+    /// Move collected protocol fees out of the treasury
+    Withdraw {
+        /// Lamports
+        #[arg(long)]
+        amount: u64,
+        /// Defaults to the signer
+        #[arg(long)]
+        recipient: Option<String>,
+    },
+    /// Hand the admin role to another key
+    TransferAdmin {
+        #[arg(long)]
+        new_admin: String,
+    },
+}
+
+/// A game given either by host and id, or by room code
+#[derive(Args)]
+struct GameRef {
+    /// Host pubkey
+    #[arg(long, required_unless_present = "room")]
+    authority: Option<String>,
+    #[arg(long, required_unless_present = "room")]
+    game_id: Option<u32>,
+    /// Room code (VEIL-XXXXXX), instead of --authority and --game-id
+    #[arg(long, conflicts_with_all = ["authority", "game_id"])]
+    room: Option<String>,
+}
+
+impl GameRef {
+    /// (authority, game_id)
+    fn resolve(&self, rpc: &RpcClient) -> Result<(Pubkey, u32)> {
+        if let Some(room_code) = &self.room {
+            let address = pda::room_index(room_code);
+            let data = rpc
+                .account_data(&address)?
+                .ok_or_else(|| anyhow!("room {} not found", room_code))?;
+            let room = RoomIndex::try_from_account_data(&data)?;
+            return Ok((room.authority, room.game_id));
+        }
+
+        match (&self.authority, self.game_id) {
+            (Some(authority), Some(game_id)) => Ok((parse_pubkey(authority)?, game_id)),
+            _ => bail!("either --room or both --authority and --game-id are required"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Choice {
+    Safe,
+    Unsafe,
+    Abstain,
+}
+
+impl From<Choice> for Ballot {
+    fn from(choice: Choice) -> Self {
+        match choice {
+            Choice::Safe => Ballot::Safe,
+            Choice::Unsafe => Ballot::Unsafe,
+            Choice::Abstain => Ballot::Abstain,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new(&cli.url);
+
+    match cli.command {
+        Command::InitCompDefs => {
+            let payer = load_keypair(&cli.keypair)?;
+            for (instruction_name, circuit) in COMP_DEFS {
+                let ix = instructions::init_comp_def(&payer.pubkey(), instruction_name, circuit);
+                let signature = rpc.send_instructions(&[ix], &payer, &[])?;
+                println!("{}: {}", circuit, signature);
+            }
+        }
+        Command::Create {
+            players,
+            room,
+            wager,
+            private_results,
+            secret_saboteur,
+            host_disclosure_delay,
+        } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let game_id = next_game_id(&rpc, &payer.pubkey())?;
+            let mut rules = 0;
+            if private_results || secret_saboteur {
+                rules |= RULE_PRIVATE_RESULTS;
+            }
+            if secret_saboteur {
+                rules |= RULE_SECRET_SABOTEUR;
+            }
+            if host_disclosure_delay.is_some() {
+                rules |= RULE_HOST_DISCLOSURE;
+            }
+            let mut ixs = vec![instructions::create_game(
+                &payer.pubkey(),
+                cluster_offset(cli.cluster_offset)?,
+                game_id,
+                players,
+                random_nonce(),
+                wager,
+                rules,
+                host_disclosure_delay.unwrap_or(0),
+            )];
+            if let Some(room_code) = &room {
+                ixs.push(instructions::register_room(&payer.pubkey(), game_id, room_code));
+            }
+            let signature = rpc.send_instructions(&ixs, &payer, &[])?;
+            println!("Created game {} at {}", game_id, pda::game(&payer.pubkey(), game_id));
+            if let Some(room_code) = room {
+                println!("Room code: {}", room_code);
+            }
+            println!("Signature: {}", signature);
+        }
+        Command::Join { game, seat } => {
+            let player = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let ix = instructions::join_game(&player.pubkey(), &authority, game_id, seat);
+            let signature = rpc.send_instructions(&[ix], &player, &[])?;
+            println!("Joined game {} in seat {}", game_id, seat);
+            println!("Signature: {}", signature);
+        }
+        Command::Vote {
+            game,
+            seat,
+            choice,
+            mxe_key,
+        } => {
+            let player = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            // Derived from the wallet so `veil result` can open a private result later
+            let keypair = VoteKeypair::derive(
+                player.secret_bytes(),
+                &pda::game(&authority, game_id),
+                seat,
+            );
+            let vote = encrypt_vote_as(&keypair, &parse_hex_key(&mxe_key)?, choice);
+            let ix = instructions::submit_vote(&player.pubkey(), &authority, game_id, seat, vote);
+            let signature = rpc.send_instructions(&[ix], &player, &[])?;
+            println!("Vote submitted for seat {} in game {}", seat, game_id);
+            println!("Ballot: {}", pda::vote(&pda::game(&authority, game_id), seat));
+            println!("Ciphertext: {}", to_hex(&vote.encrypted_vote));
+            println!("Signature: {}", signature);
+        }
+        Command::Ballot { game, seat, close } => {
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let game = pda::game(&authority, game_id);
+            if close {
+                let player = load_keypair(&cli.keypair)?;
+                let ix = instructions::close_vote(&player.pubkey(), &game, seat);
+                let signature = rpc.send_instructions(&[ix], &player, &[])?;
+                println!("Closed ballot of seat {} in game {}", seat, game_id);
+                println!("Signature: {}", signature);
+            } else {
+                let address = pda::vote(&game, seat);
+                let data = rpc
+                    .account_data(&address)?
+                    .ok_or_else(|| anyhow!("seat {} has not voted in game {}", seat, game_id))?;
+                print_ballot(&address, &VoteAccount::try_from_account_data(&data)?);
+            }
+        }
+        Command::Result { game, seat, mxe_key } => {
+            let player = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let address = pda::game(&authority, game_id);
+            let game = fetch_game(&rpc, &authority, game_id)?;
+            if !game.private_results() {
+                bail!("game {} publishes its results, see `veil status`", game_id);
+            }
+            if game.status() != Some(GameStatus::Finished) {
+                bail!("game {} has not been revealed yet", game_id);
+            }
+            let ballot = rpc
+                .account_data(&pda::vote(&address, seat))?
+                .ok_or_else(|| anyhow!("ballot of seat {} in game {} is closed", seat, game_id))?;
+            let ballot = VoteAccount::try_from_account_data(&ballot)?;
+            let keypair = VoteKeypair::derive(player.secret_bytes(), &address, seat);
+            if keypair.public != ballot.encryption_pubkey {
+                bail!("seat {} was not voted with this keypair", seat);
+            }
+            let won = decrypt_result(
+                &keypair,
+                &parse_hex_key(&mxe_key)?,
+                ballot.nonce,
+                &game.sealed_results[seat as usize],
+            );
+            println!("Seat {} {} game {}", seat, if won { "won" } else { "lost" }, game_id);
+            if game.secret_saboteur() {
+                let saboteur = decrypt_role(
+                    &keypair,
+                    &parse_hex_key(&mxe_key)?,
+                    ballot.nonce,
+                    &game.sealed_roles[seat as usize],
+                );
+                println!("Role: {}", if saboteur { "saboteur" } else { "crew" });
+            }
+        }
+        Command::Verify { game } => {
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let game = fetch_game(&rpc, &authority, game_id)?;
+            if game.status() != Some(GameStatus::Finished) {
+                bail!("game {} has not been revealed yet", game_id);
+            }
+            if game.secret_saboteur() {
+                bail!("game {} keeps its saboteur secret, its seed is not published", game_id);
+            }
+            verify_saboteur(&game.seed_commitment, &game.seed, game.num_players, game.saboteur_index)?;
+            println!("Game {}: saboteur seat {} matches the committed seed", game_id, game.saboteur_index);
+            println!("Commitment: {}", to_hex(&game.seed_commitment));
+            println!("Seed:       {}", to_hex(&game.seed));
+        }
+        Command::Disclose { game_id, mxe_key } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let address = pda::game(&payer.pubkey(), game_id);
+            let game = fetch_game(&rpc, &payer.pubkey(), game_id)?;
+            let keypair = VoteKeypair::derive_host(payer.secret_bytes(), &address);
+            if game.disclosed {
+                let saboteur = decrypt_saboteur(
+                    &keypair,
+                    &parse_hex_key(&mxe_key)?,
+                    game.disclosure_nonce,
+                    &game.host_sealed_saboteur,
+                )
+                .ok_or_else(|| anyhow!("saboteur of game {} was disclosed to another key", game_id))?;
+                println!("Saboteur of game {}: seat {}", game_id, saboteur);
+            } else {
+                let disclosure_at = game
+                    .disclosure_at()
+                    .ok_or_else(|| anyhow!("game {} cannot be disclosed", game_id))?;
+                let ix = instructions::disclose_saboteur(
+                    &payer.pubkey(),
+                    cluster_offset(cli.cluster_offset)?,
+                    game_id,
+                    game.computation_count,
+                    keypair.public,
+                    random_nonce(),
+                );
+                let signature = rpc.send_instructions(&[ix], &payer, &[])?;
+                println!("Disclosure queued for game {} (allowed from {})", game_id, disclosure_at);
+                println!("Run this command again once it completes to print the saboteur");
+                println!("Signature: {}", signature);
+            }
+        }
+        Command::Reveal {
+            game,
+            seasons,
+        } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let game = fetch_game(&rpc, &authority, game_id)?;
+            let ix = instructions::reveal_result(
+                &payer.pubkey(),
+                &authority,
+                cluster_offset(cli.cluster_offset)?,
+                game_id,
+                game.computation_count,
+                game.voted,
+                &stats_accounts(&game, &seasons),
+            );
+            let signature = rpc.send_instructions(&[ix], &payer, &[])?;
+            println!("Reveal queued for game {}", game_id);
+            println!("Signature: {}", signature);
+        }
+        Command::Retry {
+            game,
+            seasons,
+        } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let game = fetch_game(&rpc, &authority, game_id)?;
+            // A pending computation may have timed out, the program checks it did
+            let kind = game
+                .failed_computation()
+                .or_else(|| game.pending_computation().map(|(kind, _)| kind))
+                .ok_or_else(|| anyhow!("game {} has no failed computation", game_id))?;
+            let ix = instructions::retry_computation(
+                &payer.pubkey(),
+                &authority,
+                cluster_offset(cli.cluster_offset)?,
+                game_id,
+                game.computation_count,
+                kind,
+                game.voted,
+                &stats_accounts(&game, &seasons),
+            )
+            .ok_or_else(|| {
+                anyhow!(
+                    "the disclosure of game {} aborted; run `veil disclose --game-id {}` again",
+                    game_id,
+                    game_id
+                )
+            })?;
+            let signature = rpc.send_instructions(&[ix], &payer, &[])?;
+            println!("Retry of {:?} queued for game {}", kind, game_id);
+            println!("Signature: {}", signature);
+        }
+        Command::Games { host } => {
+            let host = match host {
+                Some(host) => parse_pubkey(&host)?,
+                None => load_keypair(&cli.keypair)?.pubkey(),
+            };
+            for game_id in 0..next_game_id(&rpc, &host)? {
+                let address = pda::game(&host, game_id);
+                if let Some(data) = rpc.account_data(&address)? {
+                    let game = GameAccount::try_from_account_data(&data)?;
+                    let status = match game.status() {
+                        Some(status) => format!("{:?}", status),
+                        None => format!("unknown ({})", game.status),
+                    };
+                    println!(
+                        "{:>6}  {}  {}  {}/{} votes",
+                        game_id, address, status, game.votes_received, game.num_players
+                    );
+                }
+            }
+        }
+        Command::Status { game } => {
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let game = fetch_game(&rpc, &authority, game_id)?;
+            print_game(&pda::game(&authority, game_id), &game);
+        }
+        Command::Settle { game } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let game = fetch_game(&rpc, &authority, game_id)?;
+            let seat_owners: Vec<Pubkey> = game
+                .players
+                .iter()
+                .take(game.num_players as usize)
+                .filter(|player| **player != Pubkey::default())
+                .copied()
+                .collect();
+            let ix = instructions::settle_game(&authority, game_id, &seat_owners);
+            let signature = rpc.send_instructions(&[ix], &payer, &[])?;
+            println!("Settled game {}", game_id);
+            println!("Signature: {}", signature);
+        }
+        Command::Freeze { game } => {
+            let admin = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let ix = instructions::freeze_game(&admin.pubkey(), &authority, game_id);
+            let signature = rpc.send_instructions(&[ix], &admin, &[])?;
+            println!("Froze game {}", game_id);
+            println!("Signature: {}", signature);
+        }
+        Command::Unfreeze { game } => {
+            let admin = load_keypair(&cli.keypair)?;
+            let (authority, game_id) = game.resolve(&rpc)?;
+            let ix = instructions::unfreeze_game(&admin.pubkey(), &authority, game_id);
+            let signature = rpc.send_instructions(&[ix], &admin, &[])?;
+            println!("Unfroze game {}", game_id);
+            println!("Signature: {}", signature);
+        }
+        Command::Close { game_id } => {
+            let authority = load_keypair(&cli.keypair)?;
+            let game = fetch_game(&rpc, &authority.pubkey(), game_id)?;
+            let room_code = Some(game.room_code.as_str()).filter(|code| !code.is_empty());
+            let ix = instructions::close_game(&authority.pubkey(), game_id, room_code);
+            let signature = rpc.send_instructions(&[ix], &authority, &[])?;
+            println!("Closed game {}", game_id);
+            println!("Signature: {}", signature);
+        }
+        Command::Profile { command } => match command {
+            ProfileCommand::Create => {
+                let player = load_keypair(&cli.keypair)?;
+                let ix = instructions::create_profile(&player.pubkey());
+                let signature = rpc.send_instructions(&[ix], &player, &[])?;
+                println!("Created profile {}", pda::profile(&player.pubkey()));
+                println!("Signature: {}", signature);
+            }
+            ProfileCommand::Show { player } => {
+                let player = match player {
+                    Some(player) => parse_pubkey(&player)?,
+                    None => load_keypair(&cli.keypair)?.pubkey(),
+                };
+                let address = pda::profile(&player);
+                let data = rpc
+                    .account_data(&address)?
+                    .ok_or_else(|| anyhow!("no profile for {}", player))?;
+                print_profile(&address, &PlayerProfile::try_from_account_data(&data)?);
+            }
+        },
+        Command::Season { command } => run_season(&cli.keypair, &rpc, command)?,
+        Command::Tournament { command } => run_tournament(&cli.keypair, &rpc, command)?,
+        Command::Queue { command } => run_queue(&cli.keypair, &rpc, cli.cluster_offset, command)?,
+        Command::Config { command } => run_config(&cli.keypair, &rpc, command)?,
+    }
+
+    Ok(())
+}
+
+fn run_config(keypair: &Option<PathBuf>, rpc: &RpcClient, command: ConfigCommand) -> Result<()> {
+    let ix = match command {
+        ConfigCommand::Show => {
+            print_config(&pda::config(), &fetch_config(rpc)?);
+            let address = pda::treasury();
+            if let Some(data) = rpc.account_data(&address)? {
+                print_treasury(&address, &Treasury::try_from_account_data(&data)?);
+            }
+            return Ok(());
+        }
+        ConfigCommand::Init {
+            max_players,
+            voting_period,
+            creation_fee,
+            protocol_fee_bps,
+            quorum_bps,
+            paused,
+        } => {
+            let params = ConfigParams {
+                paused,
+                max_players,
+                voting_period,
+                creation_fee,
+                protocol_fee_bps,
+                quorum_bps,
+            };
+            instructions::initialize_config(&load_keypair(keypair)?.pubkey(), params)
+        }
+        ConfigCommand::Update {
+            max_players,
+            voting_period,
+            creation_fee,
+            protocol_fee_bps,
+            quorum_bps,
+            paused,
+        } => {
+            let mut params = ConfigParams::from(&fetch_config(rpc)?);
+            params.max_players = max_players.unwrap_or(params.max_players);
+            params.voting_period = voting_period.unwrap_or(params.voting_period);
+            params.creation_fee = creation_fee.unwrap_or(params.creation_fee);
+            params.protocol_fee_bps = protocol_fee_bps.unwrap_or(params.protocol_fee_bps);
+            params.quorum_bps = quorum_bps.unwrap_or(params.quorum_bps);
+            params.paused = paused.unwrap_or(params.paused);
+            instructions::update_config(&load_keypair(keypair)?.pubkey(), params)
+        }
+        ConfigCommand::Pause => instructions::pause(&load_keypair(keypair)?.pubkey()),
+        ConfigCommand::Unpause => instructions::unpause(&load_keypair(keypair)?.pubkey()),
+        ConfigCommand::Withdraw { amount, recipient } => {
+            let admin = load_keypair(keypair)?.pubkey();
+            let recipient = match recipient {
+                Some(recipient) => parse_pubkey(&recipient)?,
+                None => admin,
+            };
+            instructions::withdraw_treasury(&admin, &recipient, amount)
+        }
+        ConfigCommand::TransferAdmin { new_admin } => instructions::transfer_admin(
+            &load_keypair(keypair)?.pubkey(),
+            &parse_pubkey(&new_admin)?,
+        ),
+    };
+
+    let admin = load_keypair(keypair)?;
+    let signature = rpc.send_instructions(&[ix], &admin, &[])?;
+    print_config(&pda::config(), &fetch_config(rpc)?);
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+fn load_keypair(path: &Option<PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.clone(),
+        None => PathBuf::from(std::env::var("HOME").context("HOME not set")?)
+            .join(".config/solana/id.json"),
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("reading keypair {}: {}", path.display(), e))
+}
+
+fn fetch_game(rpc: &RpcClient, authority: &Pubkey, game_id: u32) -> Result<GameAccount> {
+    let address = pda::game(authority, game_id);
+    let data = rpc
+        .account_data(&address)?
+        .ok_or_else(|| anyhow!("game account {} not found", address))?;
+    GameAccount::try_from_account_data(&data)
+}
+
+/// Id the program will assign to the host's next game
+fn next_game_id(rpc: &RpcClient, host: &Pubkey) -> Result<u32> {
+    match rpc.account_data(&pda::host(host))? {
+        Some(data) => Ok(HostState::try_from_account_data(&data)?.next_game_id),
+        None => Ok(0),
+    }
+}
+
+fn fetch_config(rpc: &RpcClient) -> Result<Config> {
+    let address = pda::config();
+    let data = rpc
+        .account_data(&address)?
+        .ok_or_else(|| anyhow!("config account {} not found, run `veil config init`", address))?;
+    Config::try_from_account_data(&data)
+}
+
+/// Existing profile and season entry accounts of the game's seat owners
+/// Every seat's profile PDA, existing or not, then per season in `seasons` the season and
+/// every seat's entry PDA
+fn stats_accounts(game: &GameAccount, seasons: &[u32]) -> Vec<Pubkey> {
+    let players = &game.players[..game.num_players as usize];
+    let mut accounts: Vec<Pubkey> = players.iter().map(pda::profile).collect();
+    for &season_id in seasons {
+        accounts.push(pda::season(season_id));
+        accounts.extend(players.iter().map(|player| pda::season_entry(season_id, player)));
+    }
+    accounts
+}
+
+fn run_tournament(
+    keypair: &Option<PathBuf>,
+    rpc: &RpcClient,
+    command: TournamentCommand,
+) -> Result<()> {
+    let organizer_of = |tournament: &TournamentRef| -> Result<Pubkey> {
+        match &tournament.organizer {
+            Some(organizer) => parse_pubkey(organizer),
+            None => Ok(load_keypair(keypair)?.pubkey()),
+        }
+    };
+
+    let (ix, done) = match command {
+        TournamentCommand::Create {
+            tournament_id,
+            entry_fee,
+            max_entrants,
+            prize,
+        } => {
+            let organizer = load_keypair(keypair)?.pubkey();
+            let ix = instructions::create_tournament(
+                &organizer,
+                tournament_id,
+                entry_fee,
+                max_entrants,
+                prize,
+            );
+            (ix, format!("Created tournament {}", tournament_id))
+        }
+        TournamentCommand::Register { tournament } => {
+            let player = load_keypair(keypair)?.pubkey();
+            let organizer = organizer_of(&tournament)?;
+            let ix = instructions::register_tournament(&player, &organizer, tournament.tournament_id);
+            (ix, format!("Registered for tournament {}", tournament.tournament_id))
+        }
+        TournamentCommand::Start { tournament_id } => {
+            let organizer = load_keypair(keypair)?.pubkey();
+            let ix = instructions::start_tournament(&organizer, tournament_id);
+            (ix, format!("Started tournament {}", tournament_id))
+        }
+        TournamentCommand::Seat {
+            tournament_id,
+            game_id,
+        } => {
+            let organizer = load_keypair(keypair)?.pubkey();
+            let ix = instructions::seat_tournament_game(&organizer, tournament_id, game_id);
+            (ix, format!("Seated players in game {}", game_id))
+        }
+        TournamentCommand::Confirm { game, seat } => {
+            let player = load_keypair(keypair)?.pubkey();
+            let (authority, game_id) = game.resolve(rpc)?;
+            let ix = instructions::confirm_tournament_seat(&player, &authority, game_id, seat);
+            (ix, format!("Confirmed seat {} in game {}", seat, game_id))
+        }
+        TournamentCommand::Advance { tournament } => {
+            let organizer = organizer_of(&tournament)?;
+            let state = fetch_tournament(rpc, &organizer, tournament.tournament_id)?;
+            let ix = instructions::advance_tournament(&organizer, tournament.tournament_id, &state.games);
+            (ix, format!("Advanced tournament {}", tournament.tournament_id))
+        }
+        TournamentCommand::Distribute { tournament } => {
+            let organizer = organizer_of(&tournament)?;
+            let state = fetch_tournament(rpc, &organizer, tournament.tournament_id)?;
+            let ix = instructions::distribute_tournament_prizes(
+                &organizer,
+                tournament.tournament_id,
+                &state.roster,
+            );
+            (ix, format!("Distributed prizes of tournament {}", tournament.tournament_id))
+        }
+        TournamentCommand::Show { tournament } => {
+            let organizer = organizer_of(&tournament)?;
+            let state = fetch_tournament(rpc, &organizer, tournament.tournament_id)?;
+            print_tournament(&pda::tournament(&organizer, tournament.tournament_id), &state);
+            return Ok(());
+        }
+    };
+
+    let signer = load_keypair(keypair)?;
+    let signature = rpc.send_instructions(&[ix], &signer, &[])?;
+    println!("{}", done);
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+fn fetch_tournament(rpc: &RpcClient, organizer: &Pubkey, tournament_id: u32) -> Result<Tournament> {
+    let address = pda::tournament(organizer, tournament_id);
+    let data = rpc
+        .account_data(&address)?
+        .ok_or_else(|| anyhow!("tournament account {} not found", address))?;
+    Tournament::try_from_account_data(&data)
+}
+
+fn run_queue(
+    keypair: &Option<PathBuf>,
+    rpc: &RpcClient,
+    cluster: Option<u32>,
+    command: QueueCommand,
+) -> Result<()> {
+    let (ix, done) = match command {
+        QueueCommand::Join { queue, rating_band } => {
+            let player = load_keypair(keypair)?.pubkey();
+            let rated = rpc.account_data(&pda::profile(&player))?.is_some();
+            let ix = instructions::enqueue_player(&player, queue.players, queue.stake, rating_band, rated);
+            (ix, format!("Queued for {}-player games", queue.players))
+        }
+        QueueCommand::Leave { queue } => {
+            let player = load_keypair(keypair)?.pubkey();
+            let ix = instructions::leave_queue(&player, queue.players, queue.stake);
+            (ix, format!("Left the {}-player queue", queue.players))
+        }
+        QueueCommand::Match { queue } => {
+            let payer = load_keypair(keypair)?.pubkey();
+            let game_id = next_game_id(rpc, &payer)?;
+            let ix = instructions::form_match(
+                &payer,
+                cluster_offset(cluster)?,
+                game_id,
+                queue.players,
+                queue.stake,
+                random_nonce(),
+            );
+            (ix, format!("Formed game {} at {}", game_id, pda::game(&payer, game_id)))
+        }
+        QueueCommand::Show { queue } => {
+            let address = pda::match_queue(queue.players, queue.stake);
+            let data = rpc
+                .account_data(&address)?
+                .ok_or_else(|| anyhow!("queue account {} not found", address))?;
+            print_match_queue(&address, &MatchQueue::try_from_account_data(&data)?);
+            return Ok(());
+        }
+    };
+
+    let signer = load_keypair(keypair)?;
+    let signature = rpc.send_instructions(&[ix], &signer, &[])?;
+    println!("{}", done);
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+fn run_season(keypair: &Option<PathBuf>, rpc: &RpcClient, command: SeasonCommand) -> Result<()> {
+    match command {
+        SeasonCommand::Create {
+            season_id,
+            start,
+            end,
+        } => {
+            let admin = load_keypair(keypair)?;
+            let ix = instructions::create_season(&admin.pubkey(), season_id, start, end);
+            let signature = rpc.send_instructions(&[ix], &admin, &[])?;
+            println!("Created season {}", season_id);
+            println!("Signature: {}", signature);
+        }
+        SeasonCommand::Join { season_id } => {
+            let player = load_keypair(keypair)?;
+            let ix = instructions::join_season(&player.pubkey(), season_id);
+            let signature = rpc.send_instructions(&[ix], &player, &[])?;
+            println!("Joined season {}", season_id);
+            println!("Signature: {}", signature);
+        }
+        SeasonCommand::Close { season_id } => {
+            let payer = load_keypair(keypair)?;
+            let filters = json!([
+                { "memcmp": {
+                    "offset": 0,
+                    "bytes": BASE64.encode(account_discriminator("SeasonEntry")),
+                    "encoding": "base64",
+                } },
+                { "memcmp": {
+                    "offset": SeasonEntry::SEASON_ID_OFFSET,
+                    "bytes": BASE64.encode(season_id.to_le_bytes()),
+                    "encoding": "base64",
+                } },
+            ]);
+            let mut unranked = Vec::new();
+            for (address, data) in rpc.program_accounts(&VEIL_PROGRAM_ID, filters)? {
+                if !SeasonEntry::try_from_account_data(&data)?.ranked {
+                    unranked.push(address);
+                }
+            }
+
+            // An empty batch still closes a season without entries
+            let batches: Vec<&[Pubkey]> = if unranked.is_empty() {
+                vec![&[]]
+            } else {
+                unranked.chunks(CLOSE_SEASON_BATCH).collect()
+            };
+            for batch in batches {
+                let ix = instructions::close_season(&payer.pubkey(), season_id, batch);
+                let signature = rpc.send_instructions(&[ix], &payer, &[])?;
+                println!("Ranked {} entries: {}", batch.len(), signature);
+            }
+            print_season(rpc, season_id)?;
+        }
+        SeasonCommand::Show { season_id } => print_season(rpc, season_id)?,
+    }
+    Ok(())
+}
+
+fn cluster_offset(cluster_offset: Option<u32>) -> Result<u32> {
+    cluster_offset.ok_or_else(|| anyhow!("--cluster-offset is required for this command"))
+}
+
+fn parse_pubkey(s: &str) -> Result<Pubkey> {
+    Pubkey::from_str(s).map_err(|e| anyhow!("invalid pubkey {}: {}", s, e))
+}
+
+fn parse_hex_key(s: &str) -> Result<[u8; 32]> {
+    let s = s.trim_start_matches("0x");
+    if s.len() != 64 {
+        bail!("expected 32-byte hex key, got {} characters", s.len());
+    }
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16)
+            .with_context(|| format!("invalid hex key {}", s))?;
+    }
+    Ok(key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn random_nonce() -> u128 {
+    let mut nonce = [0u8; 16];
+    OsRng.fill_bytes(&mut nonce);
+    u128::from_le_bytes(nonce)
+}
+
+fn print_game(address: &Pubkey, game: &GameAccount) {
+    println!("Game {} ({})", game.game_id, address);
+    println!("  authority:      {}", game.authority);
+    if !game.room_code.is_empty() {
+        println!("  room:           {}", game.room_code);
+    }
+    match game.status() {
+        Some(status) => println!("  status:         {:?}", status),
+        None => println!("  status:         unknown ({})", game.status),
+    }
+    if game.frozen {
+        println!("  frozen:         yes");
+    }
+    if game.matchmade {
+        println!("  matchmade:      yes");
+    }
+    if game.tournament != Pubkey::default() {
+        println!("  tournament:     {} (confirmed seats: {:#010b})", game.tournament, game.confirmed);
+    }
+    if game.wager > 0 {
+        println!("  wager:          {} lamports (pot: {})", game.wager, game.pot);
+    }
+    if game.private_results() {
+        println!("  results:        private");
+    }
+    if game.secret_saboteur() {
+        match game.disclosure_at() {
+            _ if game.disclosed => println!("  saboteur:       disclosed to the host"),
+            Some(at) => println!("  saboteur:       secret (host may disclose from {})", at),
+            None => println!("  saboteur:       secret"),
+        }
+    }
+    println!("  votes received: {}/{}", game.votes_received, game.num_players);
+    if game.voting_deadline != 0 {
+        println!(
+            "  voting closes:  {} (quorum {} bps)",
+            game.voting_deadline, game.quorum_bps
+        );
+    }
+    if game.seed_commitment != [0; 32] {
+        println!("  seed commit:    {}", to_hex(&game.seed_commitment));
+    }
+    if game.status() == Some(GameStatus::Finished) && !game.secret_saboteur() {
+        println!("  saboteur:       seat {} (seed {})", game.saboteur_index, to_hex(&game.seed));
+    }
+    if let Some((kind, offset)) = game.pending_computation() {
+        println!(
+            "  pending:        {:?} (offset {}, times out at {})",
+            kind,
+            offset,
+            game.computation_timeout_at()
+        );
+    }
+    if let Some(kind) = game.failed_computation() {
+        println!("  failed:         {:?} (retries used: {})", kind, game.retry_count);
+    }
+    for (seat, player) in game.players.iter().take(game.num_players as usize).enumerate() {
+        if *player == Pubkey::default() {
+            println!("  seat {}:         (empty)", seat);
+        } else if game.has_voted(seat as u8) {
+            println!("  seat {}:         {} (voted)", seat, player);
+        } else {
+            println!("  seat {}:         {}", seat, player);
+        }
+    }
+}
+
+fn print_config(address: &Pubkey, config: &Config) {
+    println!("Config ({})", address);
+    println!("  admin:            {}", config.admin);
+    println!("  paused:           {}", config.paused);
+    println!("  max players:      {}", config.max_players);
+    println!("  voting period:    {}s", config.voting_period);
+    println!("  creation fee:     {} lamports", config.creation_fee);
+    println!("  protocol fee:     {} bps", config.protocol_fee_bps);
+    println!("  quorum:           {} bps", config.quorum_bps);
+}
+
+fn print_treasury(address: &Pubkey, treasury: &Treasury) {
+    println!("Treasury ({})", address);
+    println!("  total collected:  {} lamports", treasury.total_collected);
+    println!("  total withdrawn:  {} lamports", treasury.total_withdrawn);
+}
+
+fn print_profile(address: &Pubkey, profile: &PlayerProfile) {
+    println!("Profile {} ({})", profile.player, address);
+    println!("  rating:           {}", profile.rating);
+    println!("  games played:     {}", profile.games_played);
+    println!("  wins:             {}", profile.wins);
+    println!(
+        "  as saboteur:      {} games, {} wins, caught {}",
+        profile.saboteur_games, profile.saboteur_wins, profile.times_caught
+    );
+    println!(
+        "  streak:           {} (best {})",
+        profile.current_streak, profile.best_streak
+    );
+}
+
+fn print_season(rpc: &RpcClient, season_id: u32) -> Result<()> {
+    let address = pda::season(season_id);
+    let data = rpc
+        .account_data(&address)?
+        .ok_or_else(|| anyhow!("season {} not found", season_id))?;
+    let season = Season::try_from_account_data(&data)?;
+
+    println!("Season {} ({})", season.season_id, address);
+    println!("  window:           {} - {}", season.start_ts, season.end_ts);
+    println!(
+        "  entries:          {} ({} ranked)",
+        season.entry_count, season.ranked_entries
+    );
+    println!("  closed:           {}", season.closed);
+
+    if let Some(data) = rpc.account_data(&pda::season_result(season_id))? {
+        let result = SeasonResult::try_from_account_data(&data)?;
+        for (rank, standing) in result.standings.iter().enumerate() {
+            println!(
+                "  #{:<2} {} score {} ({} wins / {} games)",
+                rank + 1,
+                standing.player,
+                standing.score,
+                standing.wins,
+                standing.games_played
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_ballot(address: &Pubkey, vote: &VoteAccount) {
+    println!("Ballot for seat {} ({})", vote.seat, address);
+    println!("  game:             {}", vote.game);
+    println!("  player:           {}", vote.player);
+    println!("  encryption key:   {}", to_hex(&vote.encryption_pubkey));
+    println!("  nonce:            {}", vote.nonce);
+    println!("  ciphertext:       {}", to_hex(&vote.encrypted_vote));
+    println!("  submitted at:     {}", vote.submitted_at);
+}
+
+fn print_match_queue(address: &Pubkey, queue: &MatchQueue) {
+    println!("Queue for {}-player games ({})", queue.num_players, address);
+    println!("  stake:            {} lamports", queue.stake);
+    println!("  waiting:          {}", queue.entries.len());
+    for entry in &queue.entries {
+        let band = match entry.rating_band {
+            0 => "any".to_string(),
+            band => format!("±{}", band),
+        };
+        println!(
+            "    {} rating {} band {} since {}",
+            entry.player, entry.rating, band, entry.enqueued_at
+        );
+    }
+}
+
+fn print_tournament(address: &Pubkey, tournament: &Tournament) {
+    println!("Tournament {} ({})", tournament.tournament_id, address);
+    println!("  organizer:        {}", tournament.organizer);
+    match tournament.status() {
+        Some(status) => println!("  status:           {:?}", status),
+        None => println!("  status:           unknown ({})", tournament.status),
+    }
+    println!("  round:            {} (started at {})", tournament.round, tournament.round_started_at);
+    println!("  entry fee:        {} lamports", tournament.entry_fee);
+    println!("  prize pool:       {} lamports", tournament.prize_pool);
+    println!(
+        "  players:          {} (max {}, {} seated)",
+        tournament.roster.len(),
+        tournament.max_entrants,
+        tournament.seated
+    );
+    for player in &tournament.roster {
+        println!("    {}", player);
+    }
+    for game in &tournament.games {
+        println!("  game:             {}", game);
+    }
+}
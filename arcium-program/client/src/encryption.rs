@@ -0,0 +1,240 @@
+// Client-side vote encryption (x25519 + Rescue cipher)
+// Produces the encrypted_vote / vote_encryption_pubkey / vote_nonce arguments of submit_vote
+// and opens the results private games seal to the same keys
+
+use anchor_lang::prelude::Pubkey;
+use num_bigint::BigUint;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::rescue::{self, Rescue, BLOCK_SIZE};
+
+/// Ephemeral x25519 keypair used for a single vote
+pub struct VoteKeypair {
+    secret: StaticSecret,
+    pub public: [u8; 32],
+}
+
+impl VoteKeypair {
+    pub fn generate() -> Self {
+        Self::from_secret(StaticSecret::random_from_rng(OsRng).to_bytes())
+    }
+
+    pub fn from_secret(secret: [u8; 32]) -> Self {
+        let secret = StaticSecret::from(secret);
+        let public = PublicKey::from(&secret).to_bytes();
+        Self { secret, public }
+    }
+
+    /// Per-seat keypair derived from the player's wallet secret, so the result of a
+    /// private game can be opened later without storing the vote key
+    pub fn derive(wallet_secret: &[u8], game: &Pubkey, seat: u8) -> Self {
+        Self::derive_tagged(b"veil-vote-key", wallet_secret, game, &[seat])
+    }
+
+    /// Host keypair the saboteur of a game is disclosed to, derived like `derive`
+    pub fn derive_host(wallet_secret: &[u8], game: &Pubkey) -> Self {
+        Self::derive_tagged(b"veil-host-key", wallet_secret, game, &[])
+    }
+
+    fn derive_tagged(tag: &[u8], wallet_secret: &[u8], game: &Pubkey, extra: &[u8]) -> Self {
+        let secret = Sha256::new()
+            .chain_update(tag)
+            .chain_update(wallet_secret)
+            .chain_update(game.as_ref())
+            .chain_update(extra)
+            .finalize();
+        Self::from_secret(secret.into())
+    }
+
+    /// x25519 shared secret with the MXE cluster public key
+    pub fn shared_secret(&self, mxe_public_key: &[u8; 32]) -> [u8; 32] {
+        self.secret
+            .diffie_hellman(&PublicKey::from(*mxe_public_key))
+            .to_bytes()
+    }
+}
+
+/// Rescue block cipher in counter mode over the Curve25519 base field, as Arcium's RescueCipher
+/// Plaintexts and ciphertexts are field elements, serialized as 32 LE bytes
+pub struct RescueCipher {
+    cipher: Rescue,
+}
+
+impl RescueCipher {
+    pub fn new(shared_secret: &[u8; 32]) -> Self {
+        // NIST SP 800-56C one-step KDF with Rescue-Prime: H(counter || Z || L),
+        // a single block with counter 1 and L the key length in field elements
+        let key = rescue::hash(&[
+            BigUint::from(1u8),
+            rescue::to_field(shared_secret),
+            BigUint::from(BLOCK_SIZE),
+        ]);
+        Self {
+            cipher: Rescue::cipher(&key),
+        }
+    }
+
+    /// Block i of the keystream encrypts the counter block [nonce, i, 0, 0, 0]
+    fn keystream(&self, nonce: &[u8; 16], len: usize) -> Vec<BigUint> {
+        let nonce = BigUint::from_bytes_le(nonce);
+        let mut stream = Vec::with_capacity(len);
+        let mut counter = 0u64;
+
+        while stream.len() < len {
+            let mut block = vec![BigUint::default(); BLOCK_SIZE];
+            block[0] = nonce.clone();
+            block[1] = BigUint::from(counter);
+
+            stream.extend(self.cipher.permute(&block));
+            counter += 1;
+        }
+
+        stream.truncate(len);
+        stream
+    }
+
+    pub fn encrypt(&self, plaintext: &[u128], nonce: &[u8; 16]) -> Vec<[u8; 32]> {
+        plaintext
+            .iter()
+            .zip(self.keystream(nonce, plaintext.len()))
+            .map(|(&m, k)| rescue::to_bytes(&rescue::add(&BigUint::from(m), &k)))
+            .collect()
+    }
+
+    pub fn decrypt(&self, ciphertext: &[[u8; 32]], nonce: &[u8; 16]) -> Vec<[u8; 32]> {
+        ciphertext
+            .iter()
+            .zip(self.keystream(nonce, ciphertext.len()))
+            .map(|(c, k)| rescue::to_bytes(&rescue::sub(&rescue::to_field(c), &k)))
+            .collect()
+    }
+}
+
+/// Encrypted ballot, field-for-field the trailing arguments of submit_vote
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EncryptedVote {
+    pub encrypted_vote: [u8; 32],
+    pub vote_encryption_pubkey: [u8; 32],
+    pub vote_nonce: u128,
+}
+
+impl EncryptedVote {
+    /// (encrypted_vote, vote_encryption_pubkey, vote_nonce)
+    pub fn into_args(self) -> ([u8; 32], [u8; 32], u128) {
+        (
+            self.encrypted_vote,
+            self.vote_encryption_pubkey,
+            self.vote_nonce,
+        )
+    }
+}
+
+/// Ballot as read by the reveal circuit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ballot {
+    Unsafe = 0,
+    Safe = 1,
+    Abstain = 255, // Counted for neither side; an abstaining saboteur forfeits
+}
+
+impl From<bool> for Ballot {
+    /// true = SAFE, false = UNSAFE
+    fn from(vote_safe: bool) -> Self {
+        if vote_safe {
+            Ballot::Safe
+        } else {
+            Ballot::Unsafe
+        }
+    }
+}
+
+/// Encrypts a vote for the MXE with a fresh ephemeral keypair and nonce
+/// Vote: a Ballot, or true = SAFE, false = UNSAFE
+pub fn encrypt_vote(mxe_public_key: &[u8; 32], vote: impl Into<Ballot>) -> EncryptedVote {
+    encrypt_vote_as(&VoteKeypair::generate(), mxe_public_key, vote)
+}
+
+/// encrypt_vote with a caller-supplied keypair, e.g. VoteKeypair::derive, and a fresh nonce
+pub fn encrypt_vote_as(
+    keypair: &VoteKeypair,
+    mxe_public_key: &[u8; 32],
+    vote: impl Into<Ballot>,
+) -> EncryptedVote {
+    let mut nonce = [0u8; 16];
+    OsRng.fill_bytes(&mut nonce);
+    encrypt_vote_with(keypair, nonce, mxe_public_key, vote)
+}
+
+/// Deterministic variant of encrypt_vote for a caller-supplied keypair and nonce
+pub fn encrypt_vote_with(
+    keypair: &VoteKeypair,
+    nonce: [u8; 16],
+    mxe_public_key: &[u8; 32],
+    vote: impl Into<Ballot>,
+) -> EncryptedVote {
+    let cipher = RescueCipher::new(&keypair.shared_secret(mxe_public_key));
+    let ciphertext = cipher.encrypt(&[vote.into() as u128], &nonce);
+
+    EncryptedVote {
+        encrypted_vote: ciphertext[0],
+        vote_encryption_pubkey: keypair.public,
+        vote_nonce: u128::from_le_bytes(nonce),
+    }
+}
+
+/// Nonce the reveal circuit seals a player's result under: their ballot nonce plus one
+pub fn result_nonce(vote_nonce: u128) -> [u8; 16] {
+    vote_nonce.wrapping_add(1).to_le_bytes()
+}
+
+/// Nonce the reveal circuit seals a player's role under: their ballot nonce plus two
+pub fn role_nonce(vote_nonce: u128) -> [u8; 16] {
+    vote_nonce.wrapping_add(2).to_le_bytes()
+}
+
+fn decrypt_value(
+    keypair: &VoteKeypair,
+    mxe_public_key: &[u8; 32],
+    nonce: &[u8; 16],
+    sealed: &[u8; 32],
+) -> BigUint {
+    let cipher = RescueCipher::new(&keypair.shared_secret(mxe_public_key));
+    BigUint::from_bytes_le(&cipher.decrypt(&[*sealed], nonce)[0])
+}
+
+/// Opens a seat's sealed_results entry with the keypair its ballot was encrypted with
+/// Returns true if the player won
+pub fn decrypt_result(
+    keypair: &VoteKeypair,
+    mxe_public_key: &[u8; 32],
+    vote_nonce: u128,
+    sealed_result: &[u8; 32],
+) -> bool {
+    decrypt_value(keypair, mxe_public_key, &result_nonce(vote_nonce), sealed_result)
+        == BigUint::from(1u8)
+}
+
+/// Opens a seat's sealed_roles entry like decrypt_result; true if the player was the saboteur
+pub fn decrypt_role(
+    keypair: &VoteKeypair,
+    mxe_public_key: &[u8; 32],
+    vote_nonce: u128,
+    sealed_role: &[u8; 32],
+) -> bool {
+    decrypt_value(keypair, mxe_public_key, &role_nonce(vote_nonce), sealed_role)
+        == BigUint::from(1u8)
+}
+
+/// Opens host_sealed_saboteur with the host keypair and disclosure_nonce it disclosed with
+/// None if the plaintext is not a seat, i.e. the wrong key
+pub fn decrypt_saboteur(
+    keypair: &VoteKeypair,
+    mxe_public_key: &[u8; 32],
+    disclosure_nonce: u128,
+    sealed_saboteur: &[u8; 32],
+) -> Option<u8> {
+    let seat = decrypt_value(keypair, mxe_public_key, &disclosure_nonce.to_le_bytes(), sealed_saboteur);
+    u8::try_from(&seat).ok().filter(|seat| *seat < 8)
+}
@@ -0,0 +1,282 @@
+// Typed decoder for events emitted by the Veil Arcium program
+// emit! logs "Program data: <base64(discriminator || borsh(event))>"
+
+use anchor_lang::prelude::*;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+use crate::pda::VEIL_PROGRAM_ID;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Anchor event discriminator: sha256("event:<Name>")[..8]
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("event:{}", name).as_bytes());
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&hash[..8]);
+    out
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VoteSubmittedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub votes_received: u8,
+    pub total_players: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameResultEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub saboteur_index: u8, // state::NO_SABOTEUR if secret
+    pub saboteur_voted_safe: bool, // False if private
+    pub community_voted_safe: bool, // False if private
+    pub community_won: bool,
+    pub player_results: Vec<bool>, // Per-player: did they win? Empty if private
+    pub rules: u8, // state::RULE_* bitmask
+    pub sealed_results: Vec<[u8; 32]>, // Per-player result encrypted to their ballot key, if private
+    pub sealed_roles: Vec<[u8; 32]>, // Per-player "am I the saboteur", if the saboteur is secret
+    pub seed: [u8; 32], // Selection seed, zero if the saboteur is secret
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameCreatedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub num_players: u8,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VotingOpenedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub seed_commitment: [u8; 32], // Fixes the saboteur, see fairness::verify_saboteur
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComputationQueuedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub computation_offset: u64,
+    pub kind: u8, // ComputationKind enum
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComputationAbortedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub kind: u8, // ComputationKind enum
+    pub retry_count: u8, // Retries used so far
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameClosedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameFrozenEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameUnfrozenEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeeCollectedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub kind: u8, // FeeKind enum
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct GameSettledEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub pot: u64,
+    pub protocol_fee: u64, // Including rounding dust
+    pub payout: u64, // Per paid seat
+    pub paid_seats: u8, // Bitmask by seat
+    pub refunded: bool,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TreasuryWithdrawnEvent {
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RatingUpdatedEvent {
+    pub game_id: u32,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub was_saboteur: bool,
+    pub old_rating: u32,
+    pub new_rating: u32,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SeasonClosedEvent {
+    pub season_id: u32,
+    pub entry_count: u32,
+    pub winner: Pubkey, // Default if the season had no entries
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TournamentAdvancedEvent {
+    pub tournament_id: u32,
+    pub organizer: Pubkey,
+    pub round: u8, // Round now being played, or the last one if finished
+    pub remaining_players: u8,
+    pub finished: bool,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TournamentPrizesDistributedEvent {
+    pub tournament_id: u32,
+    pub organizer: Pubkey,
+    pub prize_pool: u64,
+    pub protocol_fee: u64, // Including rounding dust
+    pub payout: u64, // Per champion
+    pub champions: u8,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MatchFormedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub stake: u64,
+    pub players: Vec<Pubkey>, // By seat
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SaboteurDisclosedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+// Generates VeilEvent plus its name / encode / decode tables from one list
+macro_rules! veil_events {
+    ($($variant:ident($event:ident)),* $(,)?) => {
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum VeilEvent {
+            $($variant($event),)*
+        }
+
+        impl VeilEvent {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(VeilEvent::$variant(_) => stringify!($event),)*
+                }
+            }
+
+            /// discriminator || borsh(event), as emitted on chain
+            pub fn to_bytes(&self) -> Vec<u8> {
+                let mut data = event_discriminator(self.name()).to_vec();
+                match self {
+                    $(VeilEvent::$variant(e) => e.serialize(&mut data),)*
+                }
+                .expect("serialize event");
+                data
+            }
+        }
+
+        /// Decodes one event payload; None for unknown discriminators or malformed data
+        pub fn decode_event(data: &[u8]) -> Option<VeilEvent> {
+            if data.len() < 8 {
+                return None;
+            }
+            let (discriminator, mut body) = data.split_at(8);
+
+            $(
+                if discriminator == event_discriminator(stringify!($event)) {
+                    return $event::deserialize(&mut body).ok().map(VeilEvent::$variant);
+                }
+            )*
+            None
+        }
+    };
+}
+
+veil_events! {
+    VoteSubmitted(VoteSubmittedEvent),
+    GameResult(GameResultEvent),
+    GameCreated(GameCreatedEvent),
+    VotingOpened(VotingOpenedEvent),
+    ComputationQueued(ComputationQueuedEvent),
+    ComputationAborted(ComputationAbortedEvent),
+    GameClosed(GameClosedEvent),
+    GameFrozen(GameFrozenEvent),
+    GameUnfrozen(GameUnfrozenEvent),
+    FeeCollected(FeeCollectedEvent),
+    GameSettled(GameSettledEvent),
+    TreasuryWithdrawn(TreasuryWithdrawnEvent),
+    RatingUpdated(RatingUpdatedEvent),
+    SeasonClosed(SeasonClosedEvent),
+    TournamentAdvanced(TournamentAdvancedEvent),
+    TournamentPrizesDistributed(TournamentPrizesDistributedEvent),
+    MatchFormed(MatchFormedEvent),
+    SaboteurDisclosed(SaboteurDisclosedEvent),
+}
+
+/// Extracts Veil events from a transaction's log messages
+/// Tracks the invocation stack so "Program data" lines from other programs are ignored
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<VeilEvent> {
+    let veil = VEIL_PROGRAM_ID.to_string();
+    let mut stack: Vec<String> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let line = line.as_ref();
+
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&veil) {
+                if let Some(event) = BASE64.decode(data).ok().and_then(|d| decode_event(&d)) {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+
+        let mut words = line.split_whitespace();
+        if let (Some("Program"), Some(program), Some(action)) =
+            (words.next(), words.next(), words.next())
+        {
+            match action {
+                "invoke" => stack.push(program.to_string()),
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
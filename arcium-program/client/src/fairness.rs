@@ -0,0 +1,28 @@
+// Verifier for the saboteur selection of the reveal_result circuit
+// init_game publishes sha256("veil-seed" || seed); reveal_result draws the saboteur from the seed
+// and publishes it, so anyone can check the saboteur was fixed before voting opened
+
+use anyhow::{bail, Result};
+pub use veil::game_logic::{seed_commitment, select_saboteur};
+
+/// Checks a revealed game: the seed opens the commitment published before voting and
+/// selects the saboteur the game reported
+pub fn verify_saboteur(
+    commitment: &[u8; 32],
+    seed: &[u8; 32],
+    num_players: u8,
+    saboteur_index: u8,
+) -> Result<()> {
+    if seed_commitment(seed) != *commitment {
+        bail!("seed does not match the commitment published when voting opened");
+    }
+    let expected = select_saboteur(seed, num_players);
+    if expected != saboteur_index {
+        bail!(
+            "seed selects seat {} but the game reported seat {}",
+            expected,
+            saboteur_index
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,622 @@
+// Instruction builders for the Veil Arcium program
+// Account order follows the #[derive(Accounts)] structs in lib_arcium.rs
+
+use anchor_lang::prelude::{borsh, AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::AnchorSerialize;
+use sha2::{Digest, Sha256};
+
+use crate::encryption::EncryptedVote;
+use crate::pda::{self, ARCIUM_PROGRAM_ID, VEIL_PROGRAM_ID};
+use crate::state::{ComputationKind, Config};
+
+/// Anchor instruction discriminator: sha256("global:<name>")[..8]
+pub fn discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("global:{}", name).as_bytes());
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&hash[..8]);
+    out
+}
+
+fn instruction(name: &str, args: impl AnchorSerialize, accounts: Vec<AccountMeta>) -> Instruction {
+    let mut data = discriminator(name).to_vec();
+    args.serialize(&mut data).expect("serialize instruction args");
+
+    Instruction {
+        program_id: VEIL_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+/// Accounts shared by every queue_computation instruction, in struct order
+fn queue_accounts(
+    payer: &Pubkey,
+    cluster_offset: u32,
+    computation_offset: u64,
+    comp_def_offset: u32,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(pda::sign_pda(), false),
+        AccountMeta::new_readonly(pda::mxe(), false),
+        AccountMeta::new(pda::mempool(cluster_offset), false),
+        AccountMeta::new(pda::execpool(cluster_offset), false),
+        AccountMeta::new(pda::computation(cluster_offset, computation_offset), false),
+        AccountMeta::new_readonly(pda::comp_def(comp_def_offset), false),
+        AccountMeta::new(pda::cluster(cluster_offset), false),
+        AccountMeta::new(pda::fee_pool(), false),
+        AccountMeta::new(pda::clock(), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(ARCIUM_PROGRAM_ID, false),
+    ]
+}
+
+/// Builds init_game_comp_def or init_reveal_comp_def
+pub fn init_comp_def(payer: &Pubkey, instruction_name: &str, circuit: &str) -> Instruction {
+    instruction(
+        instruction_name,
+        (),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(pda::mxe(), false),
+            AccountMeta::new(pda::comp_def(pda::comp_def_offset(circuit)), false),
+            AccountMeta::new_readonly(ARCIUM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// (instruction name, circuit name) for each computation definition
+pub const COMP_DEFS: [(&str, &str); 3] = [
+    ("init_game_comp_def", "init_game"),
+    ("init_reveal_comp_def", "reveal_result"),
+    ("init_disclose_comp_def", "disclose_saboteur"),
+];
+
+/// game_id: the id the program will assign, the payer's HostState::next_game_id (0 if none)
+/// rules: state::RULE_* bitmask; disclosure_delay only matters with RULE_HOST_DISCLOSURE
+#[allow(clippy::too_many_arguments)]
+pub fn create_game(
+    payer: &Pubkey,
+    cluster_offset: u32,
+    game_id: u32,
+    num_players: u8,
+    nonce: u128,
+    wager: u64,
+    rules: u8,
+    disclosure_delay: i64,
+) -> Instruction {
+    let game = pda::game(payer, game_id);
+    let mut accounts = queue_accounts(
+        payer,
+        cluster_offset,
+        pda::computation_offset(&game, 0),
+        pda::comp_def_offset("init_game"),
+    );
+    accounts.push(AccountMeta::new(pda::host(payer), false));
+    accounts.push(AccountMeta::new(game, false));
+    accounts.push(AccountMeta::new_readonly(pda::config(), false));
+    accounts.push(AccountMeta::new(pda::treasury(), false));
+
+    instruction(
+        "create_game",
+        (num_players, nonce, wager, rules, disclosure_delay),
+        accounts,
+    )
+}
+
+/// rated: pass the player's profile so the queue uses its rating, which requires it to exist
+pub fn enqueue_player(
+    player: &Pubkey,
+    num_players: u8,
+    stake: u64,
+    rating_band: u16,
+    rated: bool,
+) -> Instruction {
+    // Anchor treats the program id as "None" for optional accounts
+    let profile = if rated {
+        AccountMeta::new_readonly(pda::profile(player), false)
+    } else {
+        AccountMeta::new_readonly(VEIL_PROGRAM_ID, false)
+    };
+
+    instruction(
+        "enqueue_player",
+        (num_players, stake, rating_band),
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(pda::match_queue(num_players, stake), false),
+            profile,
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn leave_queue(player: &Pubkey, num_players: u8, stake: u64) -> Instruction {
+    instruction(
+        "leave_queue",
+        (num_players, stake),
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(pda::match_queue(num_players, stake), false),
+        ],
+    )
+}
+
+/// The payer becomes the authority of the new game; game_id as for create_game
+pub fn form_match(
+    payer: &Pubkey,
+    cluster_offset: u32,
+    game_id: u32,
+    num_players: u8,
+    stake: u64,
+    nonce: u128,
+) -> Instruction {
+    let game = pda::game(payer, game_id);
+    let mut accounts = queue_accounts(
+        payer,
+        cluster_offset,
+        pda::computation_offset(&game, 0),
+        pda::comp_def_offset("init_game"),
+    );
+    accounts.push(AccountMeta::new(pda::host(payer), false));
+    accounts.push(AccountMeta::new(game, false));
+    accounts.push(AccountMeta::new_readonly(pda::config(), false));
+    accounts.push(AccountMeta::new(pda::treasury(), false));
+    accounts.push(AccountMeta::new(pda::match_queue(num_players, stake), false));
+
+    instruction(
+        "form_match",
+        (num_players, stake, nonce),
+        accounts,
+    )
+}
+
+pub fn join_game(player: &Pubkey, authority: &Pubkey, game_id: u32, player_index: u8) -> Instruction {
+    instruction(
+        "join_game",
+        (game_id, player_index),
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*authority, false),
+            AccountMeta::new(pda::game(authority, game_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn submit_vote(
+    player: &Pubkey,
+    authority: &Pubkey,
+    game_id: u32,
+    player_index: u8,
+    vote: EncryptedVote,
+) -> Instruction {
+    let game = pda::game(authority, game_id);
+    let (encrypted_vote, vote_encryption_pubkey, vote_nonce) = vote.into_args();
+
+    instruction(
+        "submit_vote",
+        (
+            game_id,
+            player_index,
+            encrypted_vote,
+            vote_encryption_pubkey,
+            vote_nonce,
+        ),
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*authority, false),
+            AccountMeta::new(game, false),
+            AccountMeta::new(pda::vote(&game, player_index), false),
+            AccountMeta::new_readonly(pda::config(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Returns the vote account's rent to the player once the game is over
+pub fn close_vote(player: &Pubkey, game: &Pubkey, seat: u8) -> Instruction {
+    instruction(
+        "close_vote",
+        seat,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*game, false),
+            AccountMeta::new(pda::vote(game, seat), false),
+        ],
+    )
+}
+
+/// Vote accounts of the voted seats, then the stats accounts
+fn reveal_remaining_accounts(game: &Pubkey, voted: u8, stats_accounts: &[Pubkey]) -> Vec<AccountMeta> {
+    pda::votes(game, voted)
+        .into_iter()
+        .chain(stats_accounts.iter().copied())
+        .map(|a| AccountMeta::new_readonly(a, false))
+        .collect()
+}
+
+/// computation_count: the game's current GameAccount::computation_count
+/// voted: the game's GameAccount::voted, seats without a ballot abstain
+/// stats_accounts: the PlayerProfile PDA of every seat in seat order, existing or not, then per
+/// season scored the Season and every seat's SeasonEntry PDA; updated with the result
+/// unless results are private
+pub fn reveal_result(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    cluster_offset: u32,
+    game_id: u32,
+    computation_count: u32,
+    voted: u8,
+    stats_accounts: &[Pubkey],
+) -> Instruction {
+    let game = pda::game(authority, game_id);
+    let mut accounts = queue_accounts(
+        payer,
+        cluster_offset,
+        pda::computation_offset(&game, computation_count),
+        pda::comp_def_offset("reveal_result"),
+    );
+    accounts.push(AccountMeta::new_readonly(*authority, false));
+    accounts.push(AccountMeta::new(game, false));
+    accounts.extend(reveal_remaining_accounts(&game, voted, stats_accounts));
+
+    instruction("reveal_result", game_id, accounts)
+}
+
+/// Queues the disclosure of a secret saboteur to the host's x25519 key
+/// computation_count as for reveal_result
+pub fn disclose_saboteur(
+    payer: &Pubkey,
+    cluster_offset: u32,
+    game_id: u32,
+    computation_count: u32,
+    host_encryption_pubkey: [u8; 32],
+    nonce: u128,
+) -> Instruction {
+    let game = pda::game(payer, game_id);
+    let mut accounts = queue_accounts(
+        payer,
+        cluster_offset,
+        pda::computation_offset(&game, computation_count),
+        pda::comp_def_offset("disclose_saboteur"),
+    );
+    accounts.push(AccountMeta::new(game, false));
+
+    instruction(
+        "disclose_saboteur",
+        (game_id, host_encryption_pubkey, nonce),
+        accounts,
+    )
+}
+
+/// Re-queues an aborted init_game or reveal_result computation
+/// computation_count as for reveal_result; voted and stats_accounts are only used for
+/// reveal_result, as in reveal_result
+///
+/// None for ComputationKind::Disclose: an aborted disclosure is re-queued with disclose_saboteur
+#[allow(clippy::too_many_arguments)]
+pub fn retry_computation(
+    payer: &Pubkey,
+    authority: &Pubkey,
+    cluster_offset: u32,
+    game_id: u32,
+    computation_count: u32,
+    kind: ComputationKind,
+    voted: u8,
+    stats_accounts: &[Pubkey],
+) -> Option<Instruction> {
+    let (name, circuit) = match kind {
+        ComputationKind::InitGame => ("retry_init_game", "init_game"),
+        ComputationKind::Reveal => ("retry_reveal_result", "reveal_result"),
+        ComputationKind::Disclose => return None,
+    };
+
+    let game = pda::game(authority, game_id);
+    let mut accounts = queue_accounts(
+        payer,
+        cluster_offset,
+        pda::computation_offset(&game, computation_count),
+        pda::comp_def_offset(circuit),
+    );
+    accounts.push(AccountMeta::new_readonly(*authority, false));
+    accounts.push(AccountMeta::new(game, false));
+    if kind == ComputationKind::Reveal {
+        accounts.extend(reveal_remaining_accounts(&game, voted, stats_accounts));
+    }
+
+    Some(instruction(name, game_id, accounts))
+}
+
+pub fn register_room(authority: &Pubkey, game_id: u32, room_code: &str) -> Instruction {
+    instruction(
+        "register_room",
+        (game_id, room_code.to_string()),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda::game(authority, game_id), false),
+            AccountMeta::new(pda::room_index(room_code), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn create_profile(player: &Pubkey) -> Instruction {
+    instruction(
+        "create_profile",
+        (),
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(pda::profile(player), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn create_season(admin: &Pubkey, season_id: u32, start_ts: i64, end_ts: i64) -> Instruction {
+    instruction(
+        "create_season",
+        (season_id, start_ts, end_ts),
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(pda::config(), false),
+            AccountMeta::new(pda::season(season_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn join_season(player: &Pubkey, season_id: u32) -> Instruction {
+    instruction(
+        "join_season",
+        season_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new(pda::season(season_id), false),
+            AccountMeta::new(pda::season_entry(season_id, player), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// entries: a batch of unranked SeasonEntry accounts of the season
+pub fn close_season(payer: &Pubkey, season_id: u32, entries: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(pda::season(season_id), false),
+        AccountMeta::new(pda::season_result(season_id), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(entries.iter().map(|entry| AccountMeta::new(*entry, false)));
+
+    instruction("close_season", season_id, accounts)
+}
+
+pub fn create_tournament(
+    organizer: &Pubkey,
+    tournament_id: u32,
+    entry_fee: u64,
+    max_entrants: u8,
+    prize_contribution: u64,
+) -> Instruction {
+    instruction(
+        "create_tournament",
+        (tournament_id, entry_fee, max_entrants, prize_contribution),
+        vec![
+            AccountMeta::new(*organizer, true),
+            AccountMeta::new(pda::tournament(organizer, tournament_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn register_tournament(player: &Pubkey, organizer: &Pubkey, tournament_id: u32) -> Instruction {
+    instruction(
+        "register_tournament",
+        tournament_id,
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*organizer, false),
+            AccountMeta::new(pda::tournament(organizer, tournament_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn start_tournament(organizer: &Pubkey, tournament_id: u32) -> Instruction {
+    instruction(
+        "start_tournament",
+        tournament_id,
+        vec![
+            AccountMeta::new_readonly(*organizer, true),
+            AccountMeta::new(pda::tournament(organizer, tournament_id), false),
+        ],
+    )
+}
+
+pub fn seat_tournament_game(organizer: &Pubkey, tournament_id: u32, game_id: u32) -> Instruction {
+    instruction(
+        "seat_tournament_game",
+        (tournament_id, game_id),
+        vec![
+            AccountMeta::new_readonly(*organizer, true),
+            AccountMeta::new(pda::tournament(organizer, tournament_id), false),
+            AccountMeta::new(pda::game(organizer, game_id), false),
+        ],
+    )
+}
+
+pub fn confirm_tournament_seat(
+    player: &Pubkey,
+    authority: &Pubkey,
+    game_id: u32,
+    player_index: u8,
+) -> Instruction {
+    instruction(
+        "confirm_tournament_seat",
+        (game_id, player_index),
+        vec![
+            AccountMeta::new(*player, true),
+            AccountMeta::new_readonly(*authority, false),
+            AccountMeta::new(pda::game(authority, game_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// games: the round's games, in seating order
+pub fn advance_tournament(organizer: &Pubkey, tournament_id: u32, games: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*organizer, false),
+        AccountMeta::new(pda::tournament(organizer, tournament_id), false),
+    ];
+    accounts.extend(games.iter().map(|game| AccountMeta::new(*game, false)));
+
+    instruction("advance_tournament", tournament_id, accounts)
+}
+
+/// champions: the finished tournament's roster, in order
+pub fn distribute_tournament_prizes(
+    organizer: &Pubkey,
+    tournament_id: u32,
+    champions: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*organizer, false),
+        AccountMeta::new(pda::tournament(organizer, tournament_id), false),
+        AccountMeta::new_readonly(pda::config(), false),
+        AccountMeta::new(pda::treasury(), false),
+    ];
+    accounts.extend(champions.iter().map(|champion| AccountMeta::new(*champion, false)));
+
+    instruction("distribute_tournament_prizes", tournament_id, accounts)
+}
+
+/// seat_owners: the owners of all taken seats, in seat order
+pub fn settle_game(authority: &Pubkey, game_id: u32, seat_owners: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(pda::game(authority, game_id), false),
+        AccountMeta::new_readonly(pda::config(), false),
+        AccountMeta::new(pda::treasury(), false),
+    ];
+    accounts.extend(seat_owners.iter().map(|owner| AccountMeta::new(*owner, false)));
+
+    instruction("settle_game", game_id, accounts)
+}
+
+/// room_code must be the game's registered code, if any, so the room is released too
+pub fn close_game(authority: &Pubkey, game_id: u32, room_code: Option<&str>) -> Instruction {
+    // Anchor treats the program id as "None" for optional accounts
+    let room_index = match room_code {
+        Some(room_code) => AccountMeta::new(pda::room_index(room_code), false),
+        None => AccountMeta::new_readonly(VEIL_PROGRAM_ID, false),
+    };
+
+    instruction(
+        "close_game",
+        game_id,
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda::game(authority, game_id), false),
+            room_index,
+        ],
+    )
+}
+
+/// Settable config fields, as taken by initialize_config and update_config
+#[derive(AnchorSerialize, Clone, Debug)]
+pub struct ConfigParams {
+    pub paused: bool,
+    pub max_players: u8,
+    pub voting_period: i64,
+    pub creation_fee: u64,
+    pub protocol_fee_bps: u16,
+    pub quorum_bps: u16,
+}
+
+impl From<&Config> for ConfigParams {
+    fn from(config: &Config) -> Self {
+        Self {
+            paused: config.paused,
+            max_players: config.max_players,
+            voting_period: config.voting_period,
+            creation_fee: config.creation_fee,
+            protocol_fee_bps: config.protocol_fee_bps,
+            quorum_bps: config.quorum_bps,
+        }
+    }
+}
+
+/// admin must be the program's upgrade authority
+pub fn initialize_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    instruction(
+        "initialize_config",
+        params,
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(pda::config(), false),
+            AccountMeta::new(pda::treasury(), false),
+            AccountMeta::new_readonly(VEIL_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pda::program_data(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn update_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    instruction("update_config", params, admin_accounts(admin))
+}
+
+pub fn transfer_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    instruction("transfer_admin", *new_admin, admin_accounts(admin))
+}
+
+fn admin_accounts(admin: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(pda::config(), false),
+    ]
+}
+
+/// Blocks create_game and submit_vote until unpause
+pub fn pause(admin: &Pubkey) -> Instruction {
+    instruction("pause", (), admin_accounts(admin))
+}
+
+pub fn unpause(admin: &Pubkey) -> Instruction {
+    instruction("unpause", (), admin_accounts(admin))
+}
+
+pub fn freeze_game(admin: &Pubkey, authority: &Pubkey, game_id: u32) -> Instruction {
+    instruction("freeze_game", game_id, freeze_accounts(admin, authority, game_id))
+}
+
+pub fn unfreeze_game(admin: &Pubkey, authority: &Pubkey, game_id: u32) -> Instruction {
+    instruction("unfreeze_game", game_id, freeze_accounts(admin, authority, game_id))
+}
+
+fn freeze_accounts(admin: &Pubkey, authority: &Pubkey, game_id: u32) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new_readonly(pda::config(), false),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(pda::game(authority, game_id), false),
+    ]
+}
+
+pub fn withdraw_treasury(admin: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+    instruction(
+        "withdraw_treasury",
+        amount,
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(pda::config(), false),
+            AccountMeta::new(pda::treasury(), false),
+            AccountMeta::new(*recipient, false),
+        ],
+    )
+}
@@ -0,0 +1,11 @@
+// Veil client SDK
+// Off-chain helpers for building arguments to the Veil Arcium program
+
+pub mod encryption;
+pub mod events;
+pub mod fairness;
+pub mod instructions;
+pub mod pda;
+pub mod rescue;
+pub mod rpc;
+pub mod state;
@@ -0,0 +1,149 @@
+// Program-derived addresses used by the Veil Arcium program
+// Mirrors the seeds in lib_arcium.rs and the derive_*_pda! macros of arcium_anchor
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use sha2::{Digest, Sha256};
+
+pub const VEIL_PROGRAM_ID: Pubkey = Pubkey::from_str_const("51JDkhaM8nWP3NEEtDAs28WKZH8bM5Wr6YGVyuMxHfZu");
+pub const ARCIUM_PROGRAM_ID: Pubkey = Pubkey::from_str_const("ArcH9wURBPKTSYL8qpTaGh6jbDJeUVTUhXJpsCCq8RWV");
+
+pub const SIGN_PDA_SEED: &[u8] = b"SignerAccount";
+
+/// Same as arcium_anchor::comp_def_offset: first 4 bytes of sha256(name), little-endian
+pub fn comp_def_offset(name: &str) -> u32 {
+    let hash = Sha256::digest(name.as_bytes());
+    u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Offset of a game's index-th computation, as derived by the program
+pub use veil::game_logic::derive_computation_offset as computation_offset;
+
+pub fn game(authority: &Pubkey, game_id: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"game", authority.as_ref(), game_id.to_le_bytes().as_ref()],
+        &VEIL_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn vote(game: &Pubkey, seat: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"vote", game.as_ref(), &[seat]], &VEIL_PROGRAM_ID).0
+}
+
+/// VoteAccounts of the seats set in voted, in the order reveal_result expects them
+pub fn votes(game: &Pubkey, voted: u8) -> Vec<Pubkey> {
+    (0..8u8)
+        .filter(|seat| voted & (1 << seat) != 0)
+        .map(|seat| vote(game, seat))
+        .collect()
+}
+
+pub fn match_queue(num_players: u8, stake: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"match_queue", [num_players].as_ref(), stake.to_le_bytes().as_ref()],
+        &VEIL_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn host(authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"host", authority.as_ref()], &VEIL_PROGRAM_ID).0
+}
+
+pub fn config() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &VEIL_PROGRAM_ID).0
+}
+
+pub fn profile(player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"profile", player.as_ref()], &VEIL_PROGRAM_ID).0
+}
+
+pub fn season(season_id: u32) -> Pubkey {
+    Pubkey::find_program_address(&[b"season", season_id.to_le_bytes().as_ref()], &VEIL_PROGRAM_ID).0
+}
+
+pub fn season_entry(season_id: u32, player: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"season_entry", season_id.to_le_bytes().as_ref(), player.as_ref()],
+        &VEIL_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn season_result(season_id: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"season_result", season_id.to_le_bytes().as_ref()],
+        &VEIL_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn tournament(organizer: &Pubkey, tournament_id: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"tournament", organizer.as_ref(), tournament_id.to_le_bytes().as_ref()],
+        &VEIL_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn treasury() -> Pubkey {
+    Pubkey::find_program_address(&[b"treasury"], &VEIL_PROGRAM_ID).0
+}
+
+/// ProgramData account holding the Veil program's upgrade authority
+pub fn program_data() -> Pubkey {
+    Pubkey::find_program_address(&[VEIL_PROGRAM_ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+pub fn room_index(room_code: &str) -> Pubkey {
+    Pubkey::find_program_address(&[b"room", room_code.as_bytes()], &VEIL_PROGRAM_ID).0
+}
+
+pub fn sign_pda() -> Pubkey {
+    Pubkey::find_program_address(&[SIGN_PDA_SEED], &VEIL_PROGRAM_ID).0
+}
+
+fn arcium(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ARCIUM_PROGRAM_ID).0
+}
+
+pub fn mxe() -> Pubkey {
+    arcium(&[b"MXEAccount", VEIL_PROGRAM_ID.as_ref()])
+}
+
+pub fn comp_def(comp_def_offset: u32) -> Pubkey {
+    arcium(&[
+        b"ComputationDefinitionAccount",
+        VEIL_PROGRAM_ID.as_ref(),
+        comp_def_offset.to_le_bytes().as_ref(),
+    ])
+}
+
+pub fn mempool(cluster_offset: u32) -> Pubkey {
+    arcium(&[b"Mempool", cluster_offset.to_le_bytes().as_ref()])
+}
+
+pub fn execpool(cluster_offset: u32) -> Pubkey {
+    arcium(&[b"Execpool", cluster_offset.to_le_bytes().as_ref()])
+}
+
+pub fn computation(cluster_offset: u32, computation_offset: u64) -> Pubkey {
+    arcium(&[
+        b"ComputationAccount",
+        cluster_offset.to_le_bytes().as_ref(),
+        computation_offset.to_le_bytes().as_ref(),
+    ])
+}
+
+pub fn cluster(cluster_offset: u32) -> Pubkey {
+    arcium(&[b"Cluster", cluster_offset.to_le_bytes().as_ref()])
+}
+
+pub fn fee_pool() -> Pubkey {
+    arcium(&[b"FeePool"])
+}
+
+pub fn clock() -> Pubkey {
+    arcium(&[b"ClockAccount"])
+}
@@ -0,0 +1,264 @@
+// Rescue over the Curve25519 base field, as in Arcium's client library:
+// - the Rescue block cipher (keyed, with a key schedule) that RescueCipher runs in counter mode
+// - the Rescue-Prime sponge hash used to derive the cipher key from the x25519 shared secret
+// Arithmetization-friendly: the MXE cluster decrypts inside MPC cheaply
+
+use std::sync::OnceLock;
+
+use num_bigint::BigUint;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+/// Cipher block size and key length (field elements)
+pub const BLOCK_SIZE: usize = 5;
+/// Rescue-Prime hash state width (field elements)
+pub const HASH_WIDTH: usize = 12;
+/// Elements never absorbed into or squeezed from
+pub const HASH_CAPACITY: usize = 5;
+/// Elements absorbed per permutation
+pub const HASH_RATE: usize = HASH_WIDTH - HASH_CAPACITY;
+/// Field elements output by hash
+pub const DIGEST_LEN: usize = 5;
+/// Target security level in bits
+pub const SECURITY_LEVEL: usize = 128;
+/// S-box exponent: smallest prime not dividing p - 1
+pub const ALPHA: u32 = 5;
+
+/// Round constants of the cipher are derived from this seed (Rescue paper, section 4)
+const CIPHER_CONSTANTS_SEED: &str = "encrypt everything, compute anything";
+
+/// A Rescue permutation instance: the block cipher under a key, or the Rescue-Prime permutation
+pub struct Rescue {
+    cipher: bool,
+    mds: Vec<Vec<BigUint>>,
+    round_keys: Vec<Vec<BigUint>>, // 2 * rounds + 1, the first added before round one
+}
+
+impl Rescue {
+    /// Block cipher with the given key; its length is the block size.
+    /// The round keys are the states of permuting the key under the round constants
+    pub fn cipher(key: &[BigUint]) -> Self {
+        let m = key.len();
+        assert!(m >= 2, "Rescue needs a state of at least two elements");
+        let mut rescue = Self {
+            cipher: true,
+            mds: cauchy_matrix(m),
+            round_keys: cipher_round_constants(m, cipher_rounds(m)),
+        };
+        rescue.round_keys = rescue.states(key);
+        rescue
+    }
+
+    /// Rescue-Prime permutation of the given width and capacity
+    pub fn prime(m: usize, capacity: usize) -> Self {
+        Self {
+            cipher: false,
+            mds: cauchy_matrix(m),
+            round_keys: hash_round_constants(m, capacity, hash_rounds(m, capacity)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.mds.len()
+    }
+
+    /// Number of rounds; each round is two S-box layers
+    pub fn rounds(&self) -> usize {
+        (self.round_keys.len() - 1) / 2
+    }
+
+    /// Applies the permutation (in cipher mode: encrypts one block)
+    pub fn permute(&self, state: &[BigUint]) -> Vec<BigUint> {
+        self.states(state).pop().expect("at least one state")
+    }
+
+    /// Every intermediate state: the input plus the first round key, then one per half round
+    fn states(&self, state: &[BigUint]) -> Vec<Vec<BigUint>> {
+        let p = modulus();
+        assert_eq!(state.len(), self.width(), "state width");
+
+        // The cipher starts with the inverse S-box, the hash with the forward one
+        let alpha = BigUint::from(ALPHA);
+        let (even, odd) = if self.cipher {
+            (alpha_inverse(), &alpha)
+        } else {
+            (&alpha, alpha_inverse())
+        };
+
+        let mut states = vec![add_vectors(state, &self.round_keys[0])];
+        for (r, key) in self.round_keys[1..].iter().enumerate() {
+            let exponent = if r % 2 == 0 { even } else { odd };
+            let s: Vec<BigUint> = states[r].iter().map(|x| x.modpow(exponent, p)).collect();
+            states.push(add_vectors(&mat_mul(&self.mds, &s), key));
+        }
+        states
+    }
+}
+
+/// Rescue-Prime hash (HASH_WIDTH, HASH_CAPACITY), DIGEST_LEN elements of output
+/// Input is padded with a single 1 followed by zeros up to a multiple of HASH_RATE
+pub fn hash(input: &[BigUint]) -> Vec<BigUint> {
+    static PRIME: OnceLock<Rescue> = OnceLock::new();
+    let rescue = PRIME.get_or_init(|| Rescue::prime(HASH_WIDTH, HASH_CAPACITY));
+
+    let mut padded = input.to_vec();
+    padded.push(BigUint::from(1u8));
+    while !padded.len().is_multiple_of(HASH_RATE) {
+        padded.push(BigUint::default());
+    }
+
+    let mut state = vec![BigUint::default(); HASH_WIDTH];
+    for chunk in padded.chunks(HASH_RATE) {
+        for (s, x) in state.iter_mut().zip(chunk) {
+            *s = add(s, x);
+        }
+        state = rescue.permute(&state);
+    }
+
+    state.truncate(DIGEST_LEN);
+    state
+}
+
+/// Field modulus p = 2^255 - 19
+pub fn modulus() -> &'static BigUint {
+    static P: OnceLock<BigUint> = OnceLock::new();
+    P.get_or_init(|| (BigUint::from(1u8) << 255u32) - 19u32)
+}
+
+fn alpha_inverse() -> &'static BigUint {
+    static ALPHA_INV: OnceLock<BigUint> = OnceLock::new();
+    ALPHA_INV.get_or_init(|| {
+        BigUint::from(ALPHA)
+            .modinv(&(modulus() - 1u32))
+            .expect("alpha is coprime to p - 1")
+    })
+}
+
+/// Interprets little-endian bytes as a field element (reduced mod p)
+pub fn to_field(bytes: &[u8]) -> BigUint {
+    BigUint::from_bytes_le(bytes) % modulus()
+}
+
+/// Serializes a field element as 32 little-endian bytes
+pub fn to_bytes(x: &BigUint) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let le = x.to_bytes_le();
+    out[..le.len()].copy_from_slice(&le);
+    out
+}
+
+pub(crate) fn add(a: &BigUint, b: &BigUint) -> BigUint {
+    (a + b) % modulus()
+}
+
+pub(crate) fn sub(a: &BigUint, b: &BigUint) -> BigUint {
+    let p = modulus();
+    (a + (p - b % p)) % p
+}
+
+fn inverse(x: &BigUint) -> BigUint {
+    let p = modulus();
+    x.modpow(&(p - 2u32), p)
+}
+
+fn add_vectors(a: &[BigUint], b: &[BigUint]) -> Vec<BigUint> {
+    a.iter().zip(b).map(|(x, y)| add(x, y)).collect()
+}
+
+fn mat_mul(matrix: &[Vec<BigUint>], v: &[BigUint]) -> Vec<BigUint> {
+    let p = modulus();
+    matrix
+        .iter()
+        .map(|row| row.iter().zip(v).fold(BigUint::default(), |acc, (m, x)| (acc + m * x) % p))
+        .collect()
+}
+
+/// MDS matrix: the Cauchy matrix 1 / (i + j) for i, j in 1..=m
+pub fn cauchy_matrix(m: usize) -> Vec<Vec<BigUint>> {
+    (1..=m)
+        .map(|i| (1..=m).map(|j| inverse(&BigUint::from(i + j))).collect())
+        .collect()
+}
+
+/// Rounds of the block cipher: twice max(l0, l1, 5), l0 and l1 bounding the
+/// Groebner basis and interpolation attacks (Rescue paper, section 4.3)
+pub fn cipher_rounds(m: usize) -> usize {
+    let log_p = modulus().bits() as f64;
+    let l0 = ((2 * SECURITY_LEVEL) as f64
+        / ((m + 1) as f64 * (log_p - ((ALPHA - 1) as f64).log2())))
+    .ceil() as usize;
+    let l1 = if ALPHA == 3 {
+        ((SECURITY_LEVEL + 2) as f64 / (4 * m) as f64).ceil() as usize
+    } else {
+        ((SECURITY_LEVEL + 3) as f64 / (5.5 * m as f64)).ceil() as usize
+    };
+    2 * l0.max(l1).max(5)
+}
+
+/// Rounds of the Rescue-Prime permutation: the smallest round count l1 whose Groebner basis
+/// attack costs more than 2^SECURITY_LEVEL, at least 5, plus 50% (Rescue-Prime, algorithm 7)
+pub fn hash_rounds(m: usize, capacity: usize) -> usize {
+    let rate = m - capacity;
+    let dcon = |n: usize| ((ALPHA as usize - 1) * m * (n - 1)) / 2 + 2;
+    let v = |n: usize| m * (n - 1) + rate;
+    let target = BigUint::from(1u8) << SECURITY_LEVEL;
+
+    let mut l1 = 1;
+    let mut cost = binomial(v(l1) + dcon(l1), v(l1));
+    while &cost * &cost <= target && l1 <= 23 {
+        l1 += 1;
+        cost = binomial(v(l1) + dcon(l1), v(l1));
+    }
+    (3 * l1.max(5)).div_ceil(2)
+}
+
+fn binomial(n: usize, k: usize) -> BigUint {
+    (0..k).fold(BigUint::from(1u8), |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Field elements from a SHAKE256 stream: ceil(len(bin(p)) / 8) + 1 bytes each, reduced mod p
+fn sample_field_elements(seed: &str, count: usize) -> Vec<BigUint> {
+    let p = modulus();
+    let bytes_per_int = (p.bits() as usize + 2).div_ceil(8) + 1;
+    let mut shake = Shake256::default();
+    shake.update(seed.as_bytes());
+    let mut stream = vec![0u8; bytes_per_int * count];
+    shake.finalize_xof().read(&mut stream);
+    stream
+        .chunks(bytes_per_int)
+        .map(|chunk| BigUint::from_bytes_le(chunk) % p)
+        .collect()
+}
+
+/// 2 * rounds + 1 cipher round constants: an initial vector c0, then c_{r+1} = M c_r + b
+/// for a random matrix M and vector b sampled from CIPHER_CONSTANTS_SEED
+fn cipher_round_constants(m: usize, rounds: usize) -> Vec<Vec<BigUint>> {
+    let mut sampled = sample_field_elements(CIPHER_CONSTANTS_SEED, m * m + 2 * m).into_iter();
+    let matrix: Vec<Vec<BigUint>> = (0..m).map(|_| sampled.by_ref().take(m).collect()).collect();
+    let initial: Vec<BigUint> = sampled.by_ref().take(m).collect();
+    let affine: Vec<BigUint> = sampled.take(m).collect();
+
+    let mut constants = vec![initial];
+    for r in 0..2 * rounds {
+        let next = add_vectors(&mat_mul(&matrix, &constants[r]), &affine);
+        constants.push(next);
+    }
+    constants
+}
+
+/// 2 * rounds Rescue-Prime round constants from SHAKE256("Rescue-XLIX(p,m,c,s)"), after a zero
+/// vector so the permutation matches the cipher's layout
+fn hash_round_constants(m: usize, capacity: usize, rounds: usize) -> Vec<Vec<BigUint>> {
+    let seed = format!(
+        "Rescue-XLIX({},{},{},{})",
+        modulus(),
+        m,
+        capacity,
+        SECURITY_LEVEL
+    );
+    let sampled = sample_field_elements(&seed, 2 * m * rounds);
+
+    let mut constants = vec![vec![BigUint::default(); m]];
+    constants.extend(sampled.chunks(m).map(<[BigUint]>::to_vec));
+    constants
+}
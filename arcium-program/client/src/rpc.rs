@@ -0,0 +1,236 @@
+// Minimal Solana JSON-RPC client (blocking HTTP)
+// Only the handful of methods the Veil tools need
+
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+pub const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+    pub failed: bool,
+}
+
+pub struct TransactionLogs {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub logs: Vec<String>,
+}
+
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    pub fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .with_context(|| format!("{} request to {}", method, self.url))?
+            .into_json()?;
+
+        if let Some(error) = response.get("error") {
+            bail!("{} failed: {}", method, error);
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{} returned no result", method))
+    }
+
+    pub fn latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("malformed getLatestBlockhash response"))?;
+        Hash::from_str(blockhash).map_err(|e| anyhow!("invalid blockhash: {:?}", e))
+    }
+
+    /// Account data, or None if the account does not exist
+    pub fn account_data(&self, pubkey: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([pubkey.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+        if result["value"].is_null() {
+            return Ok(None);
+        }
+        let data = result["value"]["data"][0]
+            .as_str()
+            .ok_or_else(|| anyhow!("malformed getAccountInfo response"))?;
+        Ok(Some(BASE64.decode(data)?))
+    }
+
+    /// (address, data) of every account owned by program matching the getProgramAccounts filters
+    pub fn program_accounts(&self, program: &Pubkey, filters: Value) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let result = self.call(
+            "getProgramAccounts",
+            json!([program.to_string(), {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "filters": filters,
+            }]),
+        )?;
+        result
+            .as_array()
+            .ok_or_else(|| anyhow!("malformed getProgramAccounts response"))?
+            .iter()
+            .map(|account| {
+                let pubkey = account["pubkey"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("pubkey missing"))?;
+                let data = account["account"]["data"][0]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("data missing"))?;
+                Ok((
+                    Pubkey::from_str(pubkey).map_err(|e| anyhow!("invalid pubkey {}: {}", pubkey, e))?,
+                    BASE64.decode(data)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Signatures touching address, newest first
+    /// until / before bound the page exclusively, as in getSignaturesForAddress
+    pub fn signatures_for_address(
+        &self,
+        address: &Pubkey,
+        until: Option<&str>,
+        before: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SignatureInfo>> {
+        let mut config = json!({ "limit": limit, "commitment": "confirmed" });
+        if let Some(until) = until {
+            config["until"] = json!(until);
+        }
+        if let Some(before) = before {
+            config["before"] = json!(before);
+        }
+
+        let result = self.call("getSignaturesForAddress", json!([address.to_string(), config]))?;
+        result
+            .as_array()
+            .ok_or_else(|| anyhow!("malformed getSignaturesForAddress response"))?
+            .iter()
+            .map(|info| {
+                Ok(SignatureInfo {
+                    signature: info["signature"]
+                        .as_str()
+                        .ok_or_else(|| anyhow!("signature missing"))?
+                        .to_string(),
+                    slot: info["slot"].as_u64().unwrap_or_default(),
+                    failed: !info["err"].is_null(),
+                })
+            })
+            .collect()
+    }
+
+    /// Log messages of a confirmed transaction, or None if it is not available
+    pub fn transaction_logs(&self, signature: &str) -> Result<Option<TransactionLogs>> {
+        let result = self.call(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "json",
+                "commitment": "confirmed",
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )?;
+        if result.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(TransactionLogs {
+            slot: result["slot"].as_u64().unwrap_or_default(),
+            block_time: result["blockTime"].as_i64(),
+            logs: result["meta"]["logMessages"]
+                .as_array()
+                .map(|logs| {
+                    logs.iter()
+                        .filter_map(|l| l.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }))
+    }
+
+    /// Signs with payer (fee payer) plus any extra signers, sends, and waits for confirmation
+    pub fn send_instructions(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+        extra_signers: &[&Keypair],
+    ) -> Result<String> {
+        let mut signers = vec![payer];
+        signers.extend_from_slice(extra_signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &signers,
+            self.latest_blockhash()?,
+        );
+        self.send_and_confirm(&tx)
+    }
+
+    pub fn send_and_confirm(&self, tx: &Transaction) -> Result<String> {
+        let encoded = BASE64.encode(bincode::serialize(tx)?);
+        let signature = self
+            .call(
+                "sendTransaction",
+                json!([encoded, { "encoding": "base64", "preflightCommitment": "confirmed" }]),
+            )?
+            .as_str()
+            .ok_or_else(|| anyhow!("malformed sendTransaction response"))?
+            .to_string();
+
+        let started = Instant::now();
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let result = self.call("getSignatureStatuses", json!([[signature]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {} failed: {}", signature, status["err"]);
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed") | Some("finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+            sleep(POLL_INTERVAL);
+        }
+
+        bail!("transaction {} not confirmed after {:?}", signature, CONFIRM_TIMEOUT)
+    }
+}
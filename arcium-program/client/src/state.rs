@@ -0,0 +1,457 @@
+// Off-chain mirrors of the Veil Arcium program accounts
+
+use anchor_lang::prelude::*;
+use anyhow::bail;
+use sha2::{Digest, Sha256};
+
+use crate::encryption::EncryptedVote;
+
+/// Game rules bitmask, GameAccount.rules
+pub const RULE_PRIVATE_RESULTS: u8 = 1 << 0; // Only the outcome is public, see sealed_results
+pub const RULE_SECRET_SABOTEUR: u8 = 1 << 1; // Only the saboteur learns who it was, see sealed_roles
+pub const RULE_HOST_DISCLOSURE: u8 = 1 << 2; // The host may learn the secret saboteur after disclosure_delay
+pub const NO_SABOTEUR: u8 = u8::MAX; // GameResultEvent::saboteur_index of secret-saboteur games
+
+/// Anchor account discriminator: sha256("account:<Name>")[..8]
+pub fn account_discriminator(name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("account:{}", name).as_bytes());
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&hash[..8]);
+    out
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct GameAccount {
+    pub bump: u8,
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub num_players: u8,
+    pub votes_received: u8,
+    pub status: u8, // GameStatus enum
+    pub nonce: u128,
+    pub voted: u8, // Bitmask by seat of submitted ballots
+    pub players: [Pubkey; 8],
+    pub failed_computation: u8, // ComputationKind awaiting retry, NO_FAILED_COMPUTATION if none
+    pub failed_at: i64,
+    pub retry_count: u8,
+    pub pending_computation: u8, // ComputationKind awaiting its callback, NO_PENDING_COMPUTATION if none
+    pub pending_offset: u64,
+    pub computation_count: u32, // Computations queued so far, seeds the next offset
+    pub room_code: String, // Empty if no room registered
+    pub frozen: bool,
+    pub wager: u64, // Lamports per seat, 0 = no wager
+    pub pot: u64, // Escrowed wagers not yet settled
+    pub winners: u8, // Bitmask by seat, set at reveal
+    pub tournament: Pubkey, // Owning tournament, default if none
+    pub rules: u8, // RULE_* bitmask
+    pub sealed_results: [[u8; 32]; 8], // Per seat, see encryption::decrypt_result
+    pub sealed_roles: [[u8; 32]; 8], // Per seat, see encryption::decrypt_role
+    pub finished_at: i64,
+    pub disclosure_delay: i64, // Seconds after finished_at before the host may disclose the saboteur
+    pub mxe_saboteur: [u8; 32],
+    pub mxe_saboteur_nonce: u128,
+    pub disclosed: bool,
+    pub disclosure_nonce: u128,
+    pub host_sealed_saboteur: [u8; 32], // See encryption::decrypt_saboteur
+    pub seed_commitment: [u8; 32], // Published when voting opens, see fairness::verify_saboteur
+    pub mxe_seed: [[u8; 32]; 2],
+    pub mxe_seed_nonce: u128,
+    pub seed: [u8; 32], // Revealed selection seed, zero until reveal or if the saboteur is secret
+    pub saboteur_index: u8, // NO_SABOTEUR until revealed or if secret
+    pub voting_period: i64,
+    pub voting_deadline: i64, // Set when voting opens, 0 = no deadline
+    pub quorum_bps: u16, // Share of seats that must vote for a reveal after the deadline
+    pub confirmed: u8, // Bitmask by seat of accepted tournament seats
+    pub matchmade: bool, // Anyone may reveal it
+    pub votes_completed_at: i64, // 0 until every seat voted
+    pub pending_at: i64, // When the pending computation was queued
+}
+
+impl GameAccount {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("GameAccount") {
+            bail!("not a GameAccount");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+
+    pub fn status(&self) -> Option<GameStatus> {
+        GameStatus::from_u8(self.status)
+    }
+
+    pub fn has_voted(&self, seat: u8) -> bool {
+        self.voted & (1 << seat) != 0
+    }
+
+    pub fn voting_closed(&self, now: i64) -> bool {
+        self.voting_deadline != 0 && now >= self.voting_deadline
+    }
+
+    pub fn quorum_reached(&self) -> bool {
+        self.votes_received as u128 * 10_000 >= self.quorum_bps as u128 * self.num_players as u128
+    }
+
+    pub fn private_results(&self) -> bool {
+        self.rules & RULE_PRIVATE_RESULTS != 0
+    }
+
+    pub fn secret_saboteur(&self) -> bool {
+        self.rules & RULE_SECRET_SABOTEUR != 0
+    }
+
+    /// When the host may disclose the saboteur, None if the game does not allow it or
+    /// has not been revealed
+    pub fn disclosure_at(&self) -> Option<i64> {
+        (self.rules & RULE_HOST_DISCLOSURE != 0 && self.status() == Some(GameStatus::Finished))
+            .then(|| self.finished_at + self.disclosure_delay)
+    }
+
+    /// Queued computation and its offset, the only callback the game accepts
+    pub fn pending_computation(&self) -> Option<(ComputationKind, u64)> {
+        ComputationKind::from_u8(self.pending_computation).map(|kind| (kind, self.pending_offset))
+    }
+
+    /// When the pending computation counts as failed and may be retried
+    pub fn computation_timeout_at(&self) -> i64 {
+        self.pending_at + veil::game_logic::COMPUTATION_TIMEOUT_SECONDS
+    }
+
+    /// Computation that aborted and has not been retried yet
+    pub fn failed_computation(&self) -> Option<ComputationKind> {
+        ComputationKind::from_u8(self.failed_computation)
+    }
+}
+
+/// Per-authority game counter; the host's games are ids 0..next_game_id
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct HostState {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub next_game_id: u32,
+}
+
+impl HostState {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("HostState") {
+            bail!("not a HostState");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+/// One player's ballot, as encrypted by the player to the MXE
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct VoteAccount {
+    pub bump: u8,
+    pub game: Pubkey,
+    pub seat: u8,
+    pub player: Pubkey,
+    pub encryption_pubkey: [u8; 32],
+    pub nonce: u128,
+    pub encrypted_vote: [u8; 32],
+    pub submitted_at: i64,
+}
+
+impl VoteAccount {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("VoteAccount") {
+            bail!("not a VoteAccount");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+
+    /// Whether this is the ballot the player submitted
+    pub fn matches(&self, vote: &EncryptedVote) -> bool {
+        self.encrypted_vote == vote.encrypted_vote
+            && self.encryption_pubkey == vote.vote_encryption_pubkey
+            && self.nonce == vote.vote_nonce
+    }
+}
+
+/// Program-wide settings
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Config {
+    pub bump: u8,
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub max_players: u8,
+    pub voting_period: i64, // Seconds, 0 = no deadline
+    pub creation_fee: u64, // Lamports
+    pub protocol_fee_bps: u16,
+    pub quorum_bps: u16, // Share of seats that must vote for a reveal after the voting deadline
+}
+
+impl Config {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("Config") {
+            bail!("not a Config");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+/// Lifetime stats of a wallet
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct PlayerProfile {
+    pub bump: u8,
+    pub player: Pubkey,
+    pub games_played: u32,
+    pub wins: u32,
+    pub saboteur_games: u32,
+    pub saboteur_wins: u32,
+    pub times_caught: u32, // Saboteur games lost
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub rating: u32, // Elo
+}
+
+impl PlayerProfile {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("PlayerProfile") {
+            bail!("not a PlayerProfile");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+/// Competition window
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Season {
+    pub bump: u8,
+    pub season_id: u32,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub entry_count: u32,
+    pub ranked_entries: u32,
+    pub closed: bool,
+}
+
+impl Season {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("Season") {
+            bail!("not a Season");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+/// A player's score in one season
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SeasonEntry {
+    pub bump: u8,
+    pub season_id: u32,
+    pub player: Pubkey,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub score: u32,
+    pub games_played: u32,
+    pub wins: u32,
+    pub ranked: bool,
+}
+
+impl SeasonEntry {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("SeasonEntry") {
+            bail!("not a SeasonEntry");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+
+    /// Byte offset of season_id in the account data, for getProgramAccounts filters
+    pub const SEASON_ID_OFFSET: usize = 8 + 1;
+}
+
+/// Final top N of a season
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SeasonResult {
+    pub bump: u8,
+    pub season_id: u32,
+    pub standings: Vec<SeasonStanding>, // Best first
+}
+
+impl SeasonResult {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("SeasonResult") {
+            bail!("not a SeasonResult");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct SeasonStanding {
+    pub player: Pubkey,
+    pub score: u32,
+    pub wins: u32,
+    pub games_played: u32,
+}
+
+/// Public matchmaking queue; queued stakes are held on the account
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct MatchQueue {
+    pub bump: u8,
+    pub num_players: u8,
+    pub stake: u64, // Lamports per player
+    pub entries: Vec<QueueEntry>, // Oldest first
+}
+
+impl MatchQueue {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("MatchQueue") {
+            bail!("not a MatchQueue");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct QueueEntry {
+    pub player: Pubkey,
+    pub rating: u32,
+    pub rating_band: u16, // 0 = any
+    pub enqueued_at: i64,
+}
+
+/// Multi-round bracket; the prize pool is held on the account
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Tournament {
+    pub bump: u8,
+    pub tournament_id: u32,
+    pub organizer: Pubkey,
+    pub status: u8, // TournamentStatus enum
+    pub entry_fee: u64,
+    pub prize_pool: u64,
+    pub max_entrants: u8,
+    pub round: u8,
+    pub roster: Vec<Pubkey>, // Players still in, champions once finished
+    pub seated: u8,
+    pub games: Vec<Pubkey>, // This round's games, in seating order
+    pub round_started_at: i64,
+}
+
+impl Tournament {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("Tournament") {
+            bail!("not a Tournament");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+
+    pub fn status(&self) -> Option<TournamentStatus> {
+        TournamentStatus::from_u8(self.status)
+    }
+}
+
+/// Protocol fee vault; fees are held as lamports on the account
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct Treasury {
+    pub bump: u8,
+    pub total_collected: u64,
+    pub total_withdrawn: u64,
+}
+
+impl Treasury {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("Treasury") {
+            bail!("not a Treasury");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+/// Room code -> game lookup
+#[derive(AnchorDeserialize, Clone, Debug)]
+pub struct RoomIndex {
+    pub bump: u8,
+    pub room_code: String,
+    pub game: Pubkey,
+    pub authority: Pubkey,
+    pub game_id: u32,
+}
+
+impl RoomIndex {
+    pub fn try_from_account_data(data: &[u8]) -> anyhow::Result<Self> {
+        if data.len() < 8 || data[..8] != account_discriminator("RoomIndex") {
+            bail!("not a RoomIndex");
+        }
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GameStatus {
+    Lobby = 0,
+    Voting = 1,
+    Computing = 2,
+    Finished = 3,
+}
+
+impl GameStatus {
+    pub fn from_u8(status: u8) -> Option<Self> {
+        match status {
+            0 => Some(Self::Lobby),
+            1 => Some(Self::Voting),
+            2 => Some(Self::Computing),
+            3 => Some(Self::Finished),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ComputationKind {
+    InitGame = 0,
+    Reveal = 2,
+    Disclose = 3,
+}
+
+impl ComputationKind {
+    pub fn from_u8(kind: u8) -> Option<Self> {
+        match kind {
+            0 => Some(Self::InitGame),
+            2 => Some(Self::Reveal),
+            3 => Some(Self::Disclose),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FeeKind {
+    Creation = 0,
+    Pot = 1,
+    Tournament = 2, // game_id holds the tournament id
+}
+
+impl FeeKind {
+    pub fn from_u8(kind: u8) -> Option<Self> {
+        match kind {
+            0 => Some(Self::Creation),
+            1 => Some(Self::Pot),
+            2 => Some(Self::Tournament),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TournamentStatus {
+    Registration = 0,
+    Running = 1,
+    Finished = 2,
+}
+
+impl TournamentStatus {
+    pub fn from_u8(status: u8) -> Option<Self> {
+        match status {
+            0 => Some(Self::Registration),
+            1 => Some(Self::Running),
+            2 => Some(Self::Finished),
+            _ => None,
+        }
+    }
+}
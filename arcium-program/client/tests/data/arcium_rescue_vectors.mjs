@@ -0,0 +1,32 @@
+// Writes arcium_rescue_vectors.json: ballots encrypted by Arcium's reference client, checked
+// against encrypt_vote_with by tests/encryption.rs
+// Usage: npm install @arcium-hq/client && node arcium_rescue_vectors.mjs
+
+import { writeFileSync } from "node:fs";
+import { RescueCipher, x25519 } from "@arcium-hq/client";
+
+const hex = (bytes) => Buffer.from(bytes).toString("hex");
+const filled = (len, byte) => new Uint8Array(len).fill(byte);
+
+const cases = [
+  { client: 1, mxe: 2, nonce: 3, vote: 1 },
+  { client: 4, mxe: 2, nonce: 5, vote: 0 },
+  { client: 6, mxe: 7, nonce: 8, vote: 255 },
+];
+
+const vectors = cases.map(({ client, mxe, nonce, vote }) => {
+  const clientSecret = filled(32, client);
+  const mxePublicKey = x25519.getPublicKey(filled(32, mxe));
+  const nonceBytes = filled(16, nonce);
+  const shared = x25519.getSharedSecret(clientSecret, mxePublicKey);
+  const [ciphertext] = new RescueCipher(shared).encrypt([BigInt(vote)], nonceBytes);
+  return {
+    client_secret: hex(clientSecret),
+    mxe_public_key: hex(mxePublicKey),
+    nonce: hex(nonceBytes),
+    vote,
+    ciphertext: hex(ciphertext),
+  };
+});
+
+writeFileSync(new URL("./arcium_rescue_vectors.json", import.meta.url), JSON.stringify(vectors, null, 2) + "\n");
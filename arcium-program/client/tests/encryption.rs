@@ -0,0 +1,190 @@
+use num_bigint::BigUint;
+use anchor_lang::prelude::Pubkey;
+use veil_client::encryption::{
+    decrypt_result, decrypt_saboteur, encrypt_vote, encrypt_vote_as, encrypt_vote_with, Ballot,
+    result_nonce, RescueCipher, VoteKeypair,
+};
+use veil_client::rescue;
+
+fn hex(s: &str) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+    }
+    out
+}
+
+// RFC 7748 section 6.1
+#[test]
+fn x25519_known_answer() {
+    let alice = VoteKeypair::from_secret(hex(
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+    ));
+    let bob = VoteKeypair::from_secret(hex(
+        "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+    ));
+
+    assert_eq!(
+        alice.public,
+        hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert_eq!(
+        bob.public,
+        hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+    );
+
+    let shared = hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(alice.shared_secret(&bob.public), shared);
+    assert_eq!(bob.shared_secret(&alice.public), shared);
+}
+
+// Parameters of Arcium's Rescue instances over p = 2^255 - 19
+#[test]
+fn rescue_parameters() {
+    // Block cipher, m = 5: 2 * max(l0 = 1, l1 = ceil(131 / 27.5) = 5, 5)
+    assert_eq!(rescue::cipher_rounds(rescue::BLOCK_SIZE), 10);
+    // Rescue-Prime, m = 12, c = 5: l1 = 3, then ceil(1.5 * max(5, l1))
+    assert_eq!(rescue::hash_rounds(rescue::HASH_WIDTH, rescue::HASH_CAPACITY), 8);
+
+    let mds = rescue::cauchy_matrix(rescue::BLOCK_SIZE);
+    for (i, row) in mds.iter().enumerate() {
+        for (j, x) in row.iter().enumerate() {
+            assert_eq!(x * BigUint::from(i + j + 2) % rescue::modulus(), BigUint::from(1u8));
+        }
+    }
+}
+
+#[test]
+fn cipher_is_rescue_in_counter_mode() {
+    let shared = VoteKeypair::from_secret([7; 32]).shared_secret(&VoteKeypair::from_secret([9; 32]).public);
+    let nonce = [1u8; 16];
+
+    // Key: Rescue-Prime(1 || shared secret || 5); block i encrypts [nonce, i, 0, 0, 0]
+    let key = rescue::hash(&[
+        BigUint::from(1u8),
+        rescue::to_field(&shared),
+        BigUint::from(rescue::BLOCK_SIZE),
+    ]);
+    let block_cipher = rescue::Rescue::cipher(&key);
+    assert_eq!(block_cipher.rounds(), 10);
+    let block = |i: u8| {
+        let mut counter = vec![BigUint::default(); rescue::BLOCK_SIZE];
+        counter[0] = BigUint::from_bytes_le(&nonce);
+        counter[1] = BigUint::from(i);
+        block_cipher.permute(&counter)
+    };
+    let keystream: Vec<[u8; 32]> = block(0)
+        .iter()
+        .chain(&block(1)[..1])
+        .map(rescue::to_bytes)
+        .collect();
+
+    let cipher = RescueCipher::new(&shared);
+    assert_eq!(cipher.encrypt(&[0; 6], &nonce), keystream);
+    assert_eq!(
+        cipher.decrypt(&cipher.encrypt(&[3, 1, 4, 1, 5, 9], &nonce), &nonce),
+        [3u8, 1, 4, 1, 5, 9].map(|x| rescue::to_bytes(&BigUint::from(x)))
+    );
+}
+
+// Ballots encrypted by Arcium's reference client (@arcium-hq/client RescueCipher)
+#[test]
+#[ignore = "run tests/data/arcium_rescue_vectors.mjs to generate arcium_rescue_vectors.json"]
+fn matches_arcium_reference_client() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/arcium_rescue_vectors.json");
+    let vectors: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let vectors = vectors.as_array().unwrap();
+    assert!(!vectors.is_empty());
+
+    for vector in vectors {
+        let field = |name: &str| vector[name].as_str().unwrap().to_string();
+        let nonce = field("nonce");
+        let nonce: [u8; 16] = std::array::from_fn(|i| {
+            u8::from_str_radix(&nonce[2 * i..2 * i + 2], 16).unwrap()
+        });
+        let vote = match vector["vote"].as_u64().unwrap() {
+            0 => Ballot::Unsafe,
+            1 => Ballot::Safe,
+            _ => Ballot::Abstain,
+        };
+
+        let keypair = VoteKeypair::from_secret(hex(&field("client_secret")));
+        let encrypted = encrypt_vote_with(&keypair, nonce, &hex(&field("mxe_public_key")), vote);
+        assert_eq!(encrypted.encrypted_vote, hex(&field("ciphertext")), "{}", vector);
+    }
+}
+
+#[test]
+fn mxe_decrypts_vote() {
+    let mxe = VoteKeypair::generate();
+
+    for vote_safe in [true, false] {
+        let vote = encrypt_vote(&mxe.public, vote_safe);
+        let shared = mxe.shared_secret(&vote.vote_encryption_pubkey);
+        let plaintext = RescueCipher::new(&shared)
+            .decrypt(&[vote.encrypted_vote], &vote.vote_nonce.to_le_bytes());
+
+        assert_eq!(plaintext, vec![rescue::to_bytes(&BigUint::from(vote_safe as u8))]);
+    }
+}
+
+#[test]
+fn mxe_decrypts_abstention() {
+    let mxe = VoteKeypair::generate();
+    let vote = encrypt_vote(&mxe.public, Ballot::Abstain);
+    let shared = mxe.shared_secret(&vote.vote_encryption_pubkey);
+    let plaintext =
+        RescueCipher::new(&shared).decrypt(&[vote.encrypted_vote], &vote.vote_nonce.to_le_bytes());
+
+    assert_eq!(plaintext, vec![rescue::to_bytes(&BigUint::from(255u8))]);
+}
+
+#[test]
+fn fresh_keypair_and_nonce_per_vote() {
+    let mxe = VoteKeypair::generate();
+    let a = encrypt_vote(&mxe.public, true);
+    let b = encrypt_vote(&mxe.public, true);
+
+    assert_ne!(a.vote_encryption_pubkey, b.vote_encryption_pubkey);
+    assert_ne!(a.vote_nonce, b.vote_nonce);
+    assert_ne!(a.encrypted_vote, b.encrypted_vote);
+}
+
+#[test]
+fn player_opens_sealed_result() {
+    let mxe = VoteKeypair::generate();
+    let game = Pubkey::new_unique();
+    let wallet_secret = [3u8; 64];
+    let keypair = VoteKeypair::derive(&wallet_secret, &game, 2);
+
+    // Same wallet, game and seat give the same key; the vote key is not needed later
+    assert_eq!(keypair.public, VoteKeypair::derive(&wallet_secret, &game, 2).public);
+    assert_ne!(keypair.public, VoteKeypair::derive(&wallet_secret, &game, 3).public);
+
+    let vote = encrypt_vote_as(&keypair, &mxe.public, true);
+    let shared = mxe.shared_secret(&vote.vote_encryption_pubkey);
+    for won in [true, false] {
+        // As sealed by the reveal circuit
+        let sealed = RescueCipher::new(&shared).encrypt(&[won as u128], &result_nonce(vote.vote_nonce));
+
+        let reopened = VoteKeypair::derive(&wallet_secret, &game, 2);
+        assert_eq!(decrypt_result(&reopened, &mxe.public, vote.vote_nonce, &sealed[0]), won);
+    }
+}
+
+#[test]
+fn host_opens_disclosed_saboteur() {
+    let mxe = VoteKeypair::generate();
+    let game = Pubkey::new_unique();
+    let host = VoteKeypair::derive_host(&[5u8; 64], &game);
+    let nonce = 42u128;
+
+    // As sealed by the disclose_saboteur circuit
+    let shared = mxe.shared_secret(&host.public);
+    let sealed = RescueCipher::new(&shared).encrypt(&[3], &nonce.to_le_bytes());
+
+    assert_eq!(decrypt_saboteur(&host, &mxe.public, nonce, &sealed[0]), Some(3));
+    let stranger = VoteKeypair::derive_host(&[6u8; 64], &game);
+    assert_eq!(decrypt_saboteur(&stranger, &mxe.public, nonce, &sealed[0]), None);
+}
@@ -0,0 +1,59 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use anchor_lang::prelude::Pubkey;
+use base64::Engine;
+use veil_client::events::{decode_event, parse_logs, GameResultEvent, VeilEvent, VoteSubmittedEvent};
+use veil_client::pda::{ARCIUM_PROGRAM_ID, VEIL_PROGRAM_ID};
+
+fn program_data(event: &VeilEvent) -> String {
+    format!("Program data: {}", BASE64.encode(event.to_bytes()))
+}
+
+#[test]
+fn decodes_round_trip() {
+    let authority = Pubkey::new_unique();
+    let vote = VeilEvent::VoteSubmitted(VoteSubmittedEvent {
+        game_id: 7,
+        authority,
+        votes_received: 2,
+        total_players: 4,
+    });
+    let result = VeilEvent::GameResult(GameResultEvent {
+        game_id: 7,
+        authority,
+        saboteur_index: 1,
+        saboteur_voted_safe: false,
+        community_voted_safe: true,
+        community_won: true,
+        player_results: vec![true, false, true, true],
+        rules: 0,
+        sealed_results: vec![],
+        sealed_roles: vec![],
+        seed: [0; 32],
+    });
+
+    assert_eq!(decode_event(&vote.to_bytes()), Some(vote));
+    assert_eq!(decode_event(&result.to_bytes()), Some(result));
+    assert_eq!(decode_event(&[0; 16]), None);
+}
+
+#[test]
+fn parse_logs_only_accepts_veil_frames() {
+    let event = VeilEvent::VoteSubmitted(VoteSubmittedEvent {
+        game_id: 3,
+        authority: Pubkey::new_unique(),
+        votes_received: 1,
+        total_players: 3,
+    });
+    let logs = vec![
+        format!("Program {} invoke [1]", ARCIUM_PROGRAM_ID),
+        format!("Program {} invoke [2]", VEIL_PROGRAM_ID),
+        "Program log: Instruction: VoteCallback".to_string(),
+        program_data(&event),
+        format!("Program {} success", VEIL_PROGRAM_ID),
+        // Same payload logged by another program must be ignored
+        program_data(&event),
+        format!("Program {} success", ARCIUM_PROGRAM_ID),
+    ];
+
+    assert_eq!(parse_logs(&logs), vec![event]);
+}
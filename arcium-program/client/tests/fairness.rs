@@ -0,0 +1,49 @@
+use sha2::{Digest, Sha256};
+use veil_client::fairness::{seed_commitment, select_saboteur, verify_saboteur};
+
+fn first_block(seed: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(b"veil-saboteur")
+        .chain_update(seed)
+        .chain_update(0u32.to_le_bytes())
+        .finalize()
+        .into()
+}
+
+#[test]
+fn verifies_committed_seed() {
+    let seed = [11u8; 32];
+    let commitment = seed_commitment(&seed);
+    let saboteur = select_saboteur(&seed, 6);
+
+    assert!(saboteur < 6);
+    assert!(verify_saboteur(&commitment, &seed, 6, saboteur).is_ok());
+    assert!(verify_saboteur(&commitment, &seed, 6, (saboteur + 1) % 6).is_err());
+    assert!(verify_saboteur(&commitment, &[12u8; 32], 6, select_saboteur(&[12u8; 32], 6)).is_err());
+}
+
+#[test]
+fn rejects_bytes_past_the_last_full_range() {
+    // For 3 players byte 255 would make seat 0 more likely, so it is skipped
+    let seed = (0u32..)
+        .map(|i| {
+            let mut seed = [0u8; 32];
+            seed[..4].copy_from_slice(&i.to_le_bytes());
+            seed
+        })
+        .find(|seed| first_block(seed)[0] == 255 && first_block(seed)[1] != 255)
+        .unwrap();
+
+    assert_eq!(select_saboteur(&seed, 3), first_block(&seed)[1] % 3);
+}
+
+#[test]
+fn every_seat_is_drawn() {
+    for num_players in 2..=8u8 {
+        let mut seen = 0u8;
+        for i in 0..200u8 {
+            seen |= 1 << select_saboteur(&[i; 32], num_players);
+        }
+        assert_eq!(seen.count_ones(), num_players as u32);
+    }
+}
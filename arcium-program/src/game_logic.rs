@@ -0,0 +1,267 @@
+//! Game rules that do not touch accounts, shared with the Arcium program (lib_arcium.rs)
+//! Kept here so they are built and tested with this crate
+
+use anchor_lang::prelude::Pubkey;
+use solana_sha256_hasher::hashv;
+
+pub const NO_PENDING_COMPUTATION: u8 = u8::MAX;
+pub const COMPUTATION_TIMEOUT_SECONDS: i64 = 3600; // A computation with no callback by then counts as failed
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// Game rules, GameAccount.rules bitmask chosen at create_game
+pub const RULE_PRIVATE_RESULTS: u8 = 1 << 0; // Only the outcome is public, see sealed_results
+pub const RULE_SECRET_SABOTEUR: u8 = 1 << 1; // Only the saboteur learns who it was, see sealed_roles
+pub const RULE_HOST_DISCLOSURE: u8 = 1 << 2; // The host may learn the secret saboteur after disclosure_delay
+pub const KNOWN_RULES: u8 = RULE_PRIVATE_RESULTS | RULE_SECRET_SABOTEUR | RULE_HOST_DISCLOSURE;
+pub const MAX_DISCLOSURE_DELAY: i64 = 365 * 86_400; // Keeps finished_at + disclosure_delay in range
+
+// Saboteur selection, keep in sync with reveal_result.arcis; the client's fairness module re-exports it
+pub const SEED_BLOCKS: u32 = 4; // sha256 blocks of candidate bytes drawn from the seed
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RulesError {
+    Invalid,
+    PrivateResultsUnsupported,
+}
+
+/// Whether seat is set in a by-seat ballot bitmask
+pub fn has_voted(voted: u8, seat: u8) -> bool {
+    seat < 8 && voted & (1 << seat) != 0
+}
+
+/// Counts a seat's ballot in voted; returns false if the seat is out of range or was
+/// already counted
+pub fn record_vote(voted: &mut u8, num_players: u8, seat: u8) -> bool {
+    if seat >= num_players || has_voted(*voted, seat) {
+        return false;
+    }
+
+    *voted |= 1 << seat;
+    true
+}
+
+/// Offset of a game's index-th computation: first 8 bytes (LE) of
+/// sha256("computation" || game || index LE), unique per game and predictable off chain
+pub fn derive_computation_offset(game: &Pubkey, index: u32) -> u64 {
+    let hash = hashv(&[b"computation", game.as_ref(), &index.to_le_bytes()]);
+    u64::from_le_bytes(hash.to_bytes()[..8].try_into().unwrap())
+}
+
+/// sha256("veil-seed" || seed), published by init_game before any vote is cast
+pub fn seed_commitment(seed: &[u8; 32]) -> [u8; 32] {
+    hashv(&[b"veil-seed", seed]).to_bytes()
+}
+
+/// Unbiased seat draw from a seed, the same one reveal_result.arcis runs inside MPC:
+/// candidate bytes are sha256("veil-saboteur" || seed || block LE) for SEED_BLOCKS blocks,
+/// bytes at or above the largest multiple of num_players are rejected and the first accepted
+/// byte mod num_players is the seat (the last byte if all were rejected)
+pub fn select_saboteur(seed: &[u8; 32], num_players: u8) -> u8 {
+    let limit = 256 - (256 % num_players as u16);
+    let mut last = 0u8;
+
+    for block in 0..SEED_BLOCKS {
+        let bytes = hashv(&[b"veil-saboteur", seed, &block.to_le_bytes()]).to_bytes();
+        for byte in bytes {
+            if (byte as u16) < limit {
+                return byte % num_players;
+            }
+            last = byte;
+        }
+    }
+
+    last % num_players
+}
+
+/// Whether a published seed opens the commitment and replays to the reported saboteur
+pub fn seed_matches(commitment: &[u8; 32], num_players: u8, seed: &[u8; 32], saboteur_index: u8) -> bool {
+    seed_commitment(seed) == *commitment && select_saboteur(seed, num_players) == saboteur_index
+}
+
+/// Whether a voting deadline passed; 0 is no deadline, which never closes
+pub fn voting_closed(voting_deadline: i64, now: i64) -> bool {
+    voting_deadline != 0 && now >= voting_deadline
+}
+
+/// Whether at least quorum_bps of the seats voted
+pub fn quorum_reached(votes_received: u8, num_players: u8, quorum_bps: u16) -> bool {
+    votes_received as u128 * BPS_DENOMINATOR >= quorum_bps as u128 * num_players as u128
+}
+
+/// Records a queued computation as the only one whose callback is accepted; returns false
+/// if another computation is still pending
+pub fn begin_computation(pending: &mut u8, pending_offset: &mut u64, kind: u8, offset: u64) -> bool {
+    if *pending != NO_PENDING_COMPUTATION {
+        return false;
+    }
+
+    *pending = kind;
+    *pending_offset = offset;
+    true
+}
+
+/// Whether the pending computation, queued at pending_at, is overdue and will not call back
+pub fn computation_timed_out(pending: u8, pending_at: i64, now: i64) -> bool {
+    pending != NO_PENDING_COMPUTATION && now >= pending_at.saturating_add(COMPUTATION_TIMEOUT_SECONDS)
+}
+
+/// Game rules must be known and consistent: a secret saboteur needs private results
+/// (results and the majority would give it away), host disclosure needs a secret saboteur,
+/// and payouts need per-seat winners, which private results never publish.
+/// disclosure_delay is at most MAX_DISCLOSURE_DELAY
+pub fn check_rules(rules: u8, wager: u64, disclosure_delay: i64) -> Result<(), RulesError> {
+    let consistent = rules & !KNOWN_RULES == 0
+        && (rules & RULE_SECRET_SABOTEUR == 0 || rules & RULE_PRIVATE_RESULTS != 0)
+        && (rules & RULE_HOST_DISCLOSURE == 0 || rules & RULE_SECRET_SABOTEUR != 0)
+        && (0..=MAX_DISCLOSURE_DELAY).contains(&disclosure_delay);
+    if !consistent {
+        return Err(RulesError::Invalid);
+    }
+    if rules & RULE_PRIVATE_RESULTS != 0 && wager > 0 {
+        return Err(RulesError::PrivateResultsUnsupported);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ballots_are_counted_in_any_order() {
+        for num_players in 2..=8 {
+            let mut voted = 0;
+
+            // Odd seats first, then even seats backwards
+            let order = (0..num_players)
+                .filter(|seat| seat % 2 == 1)
+                .chain((0..num_players).rev().filter(|seat| seat % 2 == 0));
+            for seat in order {
+                assert!(!has_voted(voted, seat));
+                assert!(record_vote(&mut voted, num_players, seat));
+            }
+
+            assert_eq!(voted.count_ones(), num_players as u32);
+            for seat in 0..num_players {
+                assert!(has_voted(voted, seat));
+            }
+        }
+    }
+
+    #[test]
+    fn duplicate_or_invalid_ballots_are_not_counted() {
+        let mut voted = 0;
+
+        assert!(record_vote(&mut voted, 3, 1));
+        assert!(!record_vote(&mut voted, 3, 1));
+        assert!(!record_vote(&mut voted, 3, 3));
+        assert!(!record_vote(&mut voted, 3, 8));
+
+        assert_eq!(voted.count_ones(), 1);
+        assert!(!has_voted(voted, 0));
+        assert!(!has_voted(voted, 3));
+        assert!(!has_voted(voted, 8));
+    }
+
+    #[test]
+    fn only_one_computation_is_pending_at_a_time() {
+        let mut pending = NO_PENDING_COMPUTATION;
+        let mut pending_offset = 0;
+
+        assert!(begin_computation(&mut pending, &mut pending_offset, 1, 42));
+        assert!(!begin_computation(&mut pending, &mut pending_offset, 1, 43));
+        assert_eq!(pending, 1);
+        assert_eq!(pending_offset, 42);
+
+        pending = NO_PENDING_COMPUTATION;
+        assert!(begin_computation(&mut pending, &mut pending_offset, 1, 43));
+        assert_eq!(pending_offset, 43);
+    }
+
+    #[test]
+    fn pending_computations_time_out() {
+        assert!(!computation_timed_out(1, 1_000, 1_000 + COMPUTATION_TIMEOUT_SECONDS - 1));
+        assert!(computation_timed_out(1, 1_000, 1_000 + COMPUTATION_TIMEOUT_SECONDS));
+        // Nothing pending, nothing to time out
+        assert!(!computation_timed_out(NO_PENDING_COMPUTATION, 0, i64::MAX));
+        assert!(!computation_timed_out(1, i64::MAX, i64::MAX - 1));
+    }
+
+    #[test]
+    fn computation_offsets_are_unique_per_game_and_index() {
+        let game = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let offsets: Vec<u64> = (0..16).map(|i| derive_computation_offset(&game, i)).collect();
+        for (i, offset) in offsets.iter().enumerate() {
+            assert_eq!(*offset, derive_computation_offset(&game, i as u32));
+            assert!(!offsets[..i].contains(offset));
+            assert_ne!(*offset, derive_computation_offset(&other, i as u32));
+        }
+    }
+
+    #[test]
+    fn secret_saboteur_rules_must_be_consistent() {
+        let private = RULE_PRIVATE_RESULTS;
+        let secret = RULE_PRIVATE_RESULTS | RULE_SECRET_SABOTEUR;
+        let disclosed = secret | RULE_HOST_DISCLOSURE;
+
+        assert_eq!(check_rules(0, 1_000, 0), Ok(()));
+        assert_eq!(check_rules(private, 0, 0), Ok(()));
+        assert_eq!(check_rules(secret, 0, 0), Ok(()));
+        assert_eq!(check_rules(disclosed, 0, 86_400), Ok(()));
+
+        // Unknown bits, a secret saboteur with public results, disclosure of a public saboteur
+        assert_eq!(check_rules(1 << 7, 0, 0), Err(RulesError::Invalid));
+        assert_eq!(check_rules(RULE_SECRET_SABOTEUR, 0, 0), Err(RulesError::Invalid));
+        assert_eq!(check_rules(private | RULE_HOST_DISCLOSURE, 0, 0), Err(RulesError::Invalid));
+        assert_eq!(check_rules(disclosed, 0, -1), Err(RulesError::Invalid));
+        // A delay that could overflow finished_at + disclosure_delay
+        assert_eq!(check_rules(disclosed, 0, MAX_DISCLOSURE_DELAY), Ok(()));
+        assert_eq!(check_rules(disclosed, 0, MAX_DISCLOSURE_DELAY + 1), Err(RulesError::Invalid));
+        assert_eq!(check_rules(disclosed, 0, i64::MAX), Err(RulesError::Invalid));
+        // Private results cannot be paid out per seat
+        assert_eq!(check_rules(secret, 1_000, 0), Err(RulesError::PrivateResultsUnsupported));
+    }
+
+    #[test]
+    fn saboteur_is_fixed_by_the_committed_seed() {
+        let seed = [11u8; 32];
+        let commitment = seed_commitment(&seed);
+        let saboteur = select_saboteur(&seed, 5);
+
+        assert!(saboteur < 5);
+        assert!(seed_matches(&commitment, 5, &seed, saboteur));
+        assert!(!seed_matches(&commitment, 5, &seed, (saboteur + 1) % 5));
+        assert!(!seed_matches(&commitment, 5, &[12u8; 32], select_saboteur(&[12u8; 32], 5)));
+    }
+
+    #[test]
+    fn saboteur_selection_is_unbiased() {
+        // 256 % 3 == 1: byte 255 is rejected rather than counted for seat 0
+        let mut counts = [0u32; 3];
+        for i in 0..30_000u32 {
+            let mut seed = [0u8; 32];
+            seed[..4].copy_from_slice(&i.to_le_bytes());
+            counts[select_saboteur(&seed, 3) as usize] += 1;
+        }
+        for count in counts {
+            assert!((9_500..10_500).contains(&count), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn reveal_goes_ahead_on_quorum_after_the_deadline() {
+        // Three quarters of four seats
+        assert!(!quorum_reached(2, 4, 7_500));
+        assert!(quorum_reached(3, 4, 7_500));
+        // Every seat must vote at the maximum quorum
+        assert!(!quorum_reached(3, 4, BPS_DENOMINATOR as u16));
+        assert!(quorum_reached(4, 4, BPS_DENOMINATOR as u16));
+
+        assert!(!voting_closed(1_000, 999));
+        assert!(voting_closed(1_000, 1_000));
+        // Without a deadline voting never closes, every seat has to vote
+        assert!(!voting_closed(0, i64::MAX));
+    }
+}
@@ -1,11 +1,68 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use arcium_anchor::prelude::*;
-use arcium_client::idl::arcium::types::CallbackAccount;
+use arcium_client::idl::arcium::types::{ArgumentList, CallbackAccount};
+
+pub mod game_logic;
+
+pub use game_logic::{derive_computation_offset, seed_commitment, select_saboteur};
+use game_logic::{
+    RulesError, BPS_DENOMINATOR, COMPUTATION_TIMEOUT_SECONDS, NO_PENDING_COMPUTATION,
+    RULE_HOST_DISCLOSURE, RULE_PRIVATE_RESULTS, RULE_SECRET_SABOTEUR,
+};
 
 // Computation definition offsets
 const COMP_DEF_OFFSET_INIT_GAME: u32 = comp_def_offset("init_game");
-const COMP_DEF_OFFSET_VOTE: u32 = comp_def_offset("vote");
 const COMP_DEF_OFFSET_REVEAL: u32 = comp_def_offset("reveal_result");
+const COMP_DEF_OFFSET_DISCLOSE: u32 = comp_def_offset("disclose_saboteur");
+
+// Aborted computation retries
+const MAX_COMPUTATION_RETRIES: u8 = 3;
+const RETRY_COOLDOWN_SECONDS: i64 = 300; // After this anyone may retry, not just the authority
+const REVEAL_TIMEOUT_SECONDS: i64 = 3600; // After this anyone may reveal, not just the authority
+const NO_FAILED_COMPUTATION: u8 = u8::MAX;
+
+// Room codes as generated by the server: "VEIL-" + 6 chars
+const ROOM_CODE_PREFIX: &str = "VEIL-";
+const ROOM_CODE_SUFFIX_LEN: usize = 6;
+const ROOM_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+// Program config
+const MAX_PLAYERS: u8 = 8; // Size of players
+
+// Ballot fields of a VoteAccount as read by the reveal circuit
+const BALLOT_OFFSET: u32 = 8 + 1 + 32 + 1 + 32; // discriminator, bump, game, seat, player
+const BALLOT_LEN: u32 = 32 + 16 + 32; // encryption_pubkey, nonce, encrypted_vote
+
+// Game rules: RULE_* live in game_logic
+const NO_SABOTEUR: u8 = u8::MAX; // Published saboteur_index of secret-saboteur games
+
+const MAX_PROTOCOL_FEE_BPS: u16 = 10_000;
+const MAX_QUORUM_BPS: u16 = 10_000; // Every seat must vote
+
+// Elo rating, crew vs saboteur as two teams
+const DEFAULT_RATING: u32 = 1200; // Rating of a new profile, and of queue entries without one
+const MIN_RATING: u32 = 100;
+const RATING_K_FACTOR: i64 = 32;
+const ELO_TABLE_STEP: i64 = 50;
+// Expected score in per-mille for rating differences 0, 50, ..., 800
+const ELO_EXPECTED_PERMILLE: [i64; 17] = [
+    500, 571, 640, 703, 760, 808, 849, 882, 909, 930, 947, 960, 969, 977, 983, 987, 990,
+];
+
+// Seasons
+const SEASON_TOP_N: usize = 10; // Keep in sync with SeasonResult max_len
+const SEASON_WIN_POINTS: u32 = 3;
+const SEASON_PLAY_POINTS: u32 = 1;
+
+// Matchmaking
+const MAX_QUEUE_ENTRIES: usize = 32; // Keep in sync with MatchQueue max_len
+
+// Tournaments
+const MAX_TOURNAMENT_ENTRANTS: usize = 32; // Keep in sync with Tournament max_len
+const MAX_TOURNAMENT_GAMES: usize = 16;
+const MAX_TOURNAMENT_ROUNDS: u8 = 8;
+const TOURNAMENT_ROUND_TIMEOUT: i64 = 7 * 24 * 60 * 60; // Seconds before a stuck round can be advanced
 
 declare_id!("51JDkhaM8nWP3NEEtDAs28WKZH8bM5Wr6YGVyuMxHfZu");
 
@@ -20,26 +77,70 @@ pub mod veil {
     }
 
     /// Creates a new voting game session
-    /// Initializes encrypted vote counters using MPC
+    /// The game id is assigned from the payer's HostState and reported in GameCreatedEvent
     /// Selects random saboteur inside MPC enclave (server never knows)
+    /// rules: RULE_* bitmask, see check_rules
+    /// disclosure_delay: seconds after the reveal before the host may disclose the saboteur,
+    /// at most MAX_DISCLOSURE_DELAY
     pub fn create_game(
         ctx: Context<CreateGame>,
-        computation_offset: u64,
-        game_id: u32,
         num_players: u8,
         nonce: u128,
+        wager: u64,
+        rules: u8,
+        disclosure_delay: i64,
     ) -> Result<()> {
         msg!("Creating new Veil game with {} players", num_players);
 
-        require!(num_players >= 2 && num_players <= 8, ErrorCode::InvalidPlayerCount);
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        require!(
+            num_players >= 2 && num_players <= ctx.accounts.config.max_players,
+            ErrorCode::InvalidPlayerCount
+        );
+        check_rules(rules, wager, disclosure_delay)?;
+
+        let game_id = ctx.accounts.host_state.allocate_game_id(
+            ctx.bumps.host_state,
+            ctx.accounts.payer.key(),
+        );
 
         ctx.accounts.game_account.game_id = game_id;
         ctx.accounts.game_account.bump = ctx.bumps.game_account;
         ctx.accounts.game_account.authority = ctx.accounts.payer.key();
         ctx.accounts.game_account.num_players = num_players;
         ctx.accounts.game_account.nonce = nonce;
-        ctx.accounts.game_account.vote_state = [[0; 32]; 8]; // Max 8 players
+        ctx.accounts.game_account.voted = 0;
+        ctx.accounts.game_account.players = [Pubkey::default(); 8];
+        ctx.accounts.game_account.failed_computation = NO_FAILED_COMPUTATION;
+        ctx.accounts.game_account.pending_computation = NO_PENDING_COMPUTATION;
         ctx.accounts.game_account.status = GameStatus::Lobby as u8;
+        ctx.accounts.game_account.wager = wager;
+        ctx.accounts.game_account.rules = rules;
+        ctx.accounts.game_account.saboteur_index = NO_SABOTEUR;
+        ctx.accounts.game_account.voting_period = ctx.accounts.config.voting_period;
+        ctx.accounts.game_account.quorum_bps = ctx.accounts.config.quorum_bps;
+        ctx.accounts.game_account.disclosure_delay = disclosure_delay;
+
+        let game_key = ctx.accounts.game_account.key();
+        let computation_offset = ctx
+            .accounts
+            .game_account
+            .begin_computation(game_key, ComputationKind::InitGame)?;
+
+        let creation_fee = ctx.accounts.config.creation_fee;
+        if creation_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                creation_fee,
+            )?;
+            ctx.accounts.treasury.total_collected += creation_fee;
+        }
 
         let args = ArgBuilder::new()
             .plaintext_u128(nonce)
@@ -65,6 +166,33 @@ pub mod veil {
             0,
         )?;
 
+        let slot = Clock::get()?.slot;
+
+        emit!(GameCreatedEvent {
+            game_id,
+            authority: ctx.accounts.game_account.authority,
+            num_players,
+            slot,
+        });
+
+        if creation_fee > 0 {
+            emit!(FeeCollectedEvent {
+                game_id,
+                authority: ctx.accounts.game_account.authority,
+                kind: FeeKind::Creation as u8,
+                amount: creation_fee,
+                slot,
+            });
+        }
+
+        emit!(ComputationQueuedEvent {
+            game_id,
+            authority: ctx.accounts.game_account.authority,
+            computation_offset,
+            kind: ComputationKind::InitGame as u8,
+            slot,
+        });
+
         Ok(())
     }
 
@@ -73,59 +201,274 @@ pub mod veil {
         ctx: Context<InitGameCallback>,
         output: SignedComputationOutputs<InitGameOutput>,
     ) -> Result<()> {
+        // The accounts constraints matched this computation to the game, it is no longer pending
+        ctx.accounts.game_account.end_computation();
+
         let o = match output.verify_output(
             &ctx.accounts.cluster_account,
             &ctx.accounts.computation_account,
         ) {
             Ok(InitGameOutput { field_0 }) => field_0,
-            Err(_) => return Err(ErrorCode::AbortedComputation.into()),
+            Err(_) => {
+                let clock = Clock::get()?;
+                let game = &mut ctx.accounts.game_account;
+                game.record_failure(ComputationKind::InitGame, clock.unix_timestamp);
+
+                emit!(ComputationAbortedEvent {
+                    game_id: game.game_id,
+                    authority: game.authority,
+                    kind: ComputationKind::InitGame as u8,
+                    retry_count: game.retry_count,
+                    slot: clock.slot,
+                });
+                return Ok(());
+            }
         };
 
-        ctx.accounts.game_account.vote_state = o.ciphertexts;
-        ctx.accounts.game_account.nonce = o.nonce;
+        // The saboteur is drawn from this seed at reveal; the commitment fixes it before voting
+        ctx.accounts.game_account.seed_commitment = o.seed_commitment;
+        ctx.accounts.game_account.mxe_seed = o.mxe_seed.ciphertexts;
+        ctx.accounts.game_account.mxe_seed_nonce = o.mxe_seed.nonce;
         ctx.accounts.game_account.status = GameStatus::Voting as u8;
+        ctx.accounts.game_account.clear_failure();
+
+        // The voting window starts when ballots can first be cast
+        let now = Clock::get()?.unix_timestamp;
+        let game = &mut ctx.accounts.game_account;
+        game.voting_deadline = if game.voting_period > 0 { now + game.voting_period } else { 0 };
+
+        emit!(VotingOpenedEvent {
+            game_id: ctx.accounts.game_account.game_id,
+            authority: ctx.accounts.game_account.authority,
+            seed_commitment: o.seed_commitment,
+            slot: Clock::get()?.slot,
+        });
 
         Ok(())
     }
 
-    // ===== VOTE COMPUTATION DEFINITION =====
-    pub fn init_vote_comp_def(ctx: Context<InitVoteCompDef>) -> Result<()> {
-        init_comp_def(ctx.accounts, None, None)?;
+    /// Claim a seat in the game
+    /// Only the seat owner can submit the vote for that seat
+    pub fn join_game(ctx: Context<JoinGame>, game_id: u32, player_index: u8) -> Result<()> {
+        let game = &mut ctx.accounts.game_account;
+
+        require!(!game.frozen, ErrorCode::GameFrozen);
+        require!(
+            game.status == GameStatus::Lobby as u8 || game.status == GameStatus::Voting as u8,
+            ErrorCode::InvalidGameStatus
+        );
+
+        require!(player_index < game.num_players, ErrorCode::InvalidPlayerIndex);
+        require!(game.players[player_index as usize] == Pubkey::default(), ErrorCode::SeatTaken);
+        require!(!game.players.contains(&ctx.accounts.player.key()), ErrorCode::AlreadyJoined);
+
+        game.players[player_index as usize] = ctx.accounts.player.key();
+        let wager = game.wager;
+
+        // Wagers are escrowed in the game account until settle_game
+        if wager > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.game_account.to_account_info(),
+                    },
+                ),
+                wager,
+            )?;
+            ctx.accounts.game_account.pot += wager;
+        }
+
+        msg!("Player {} joined game {} in seat {}", ctx.accounts.player.key(), game_id, player_index);
+
         Ok(())
     }
 
-    /// Submit encrypted vote
-    /// Vote is encrypted client-side using x25519 + Rescue cipher
-    /// Server receives only ciphertext
-    pub fn submit_vote(
-        ctx: Context<SubmitVote>,
-        computation_offset: u64,
-        game_id: u32,
-        player_index: u8,
-        encrypted_vote: [u8; 32],
-        vote_encryption_pubkey: [u8; 32],
-        vote_nonce: u128,
+    /// Register a human-friendly room code (VEIL-ABC123) for a game
+    /// Players resolve the code to the game account without knowing the host's pubkey
+    pub fn register_room(ctx: Context<RegisterRoom>, game_id: u32, room_code: String) -> Result<()> {
+        require!(is_valid_room_code(&room_code), ErrorCode::InvalidRoomCode);
+        require!(ctx.accounts.game_account.room_code.is_empty(), ErrorCode::RoomAlreadyRegistered);
+
+        // Released codes are closed accounts, so a live game here means a collision
+        require!(
+            ctx.accounts.room_index.game == Pubkey::default(),
+            ErrorCode::RoomCodeTaken
+        );
+
+        ctx.accounts.room_index.bump = ctx.bumps.room_index;
+        ctx.accounts.room_index.room_code = room_code.clone();
+        ctx.accounts.room_index.game = ctx.accounts.game_account.key();
+        ctx.accounts.room_index.authority = ctx.accounts.authority.key();
+        ctx.accounts.room_index.game_id = game_id;
+
+        ctx.accounts.game_account.room_code = room_code;
+
+        msg!("Registered room {} for game {}", ctx.accounts.room_index.room_code, game_id);
+
+        Ok(())
+    }
+
+    // ===== MATCHMAKING =====
+
+    /// Wait in the public queue for num_players-player games staking `stake` lamports each
+    /// The stake is escrowed in the queue; rating comes from the player's profile if passed
+    /// rating_band: largest rating gap accepted to any other player in the match, 0 = any
+    pub fn enqueue_player(
+        ctx: Context<EnqueuePlayer>,
+        num_players: u8,
+        stake: u64,
+        rating_band: u16,
     ) -> Result<()> {
         require!(
-            ctx.accounts.game_account.status == GameStatus::Voting as u8,
-            ErrorCode::InvalidGameStatus
+            num_players >= 2 && num_players <= MAX_PLAYERS,
+            ErrorCode::InvalidPlayerCount
         );
 
-        require!(player_index < ctx.accounts.game_account.num_players, ErrorCode::InvalidPlayerIndex);
+        let player = ctx.accounts.player.key();
+        let queue = &ctx.accounts.match_queue;
+        require!(
+            queue.entries.iter().all(|e| e.player != player),
+            ErrorCode::AlreadyJoined
+        );
+        require!(queue.entries.len() < MAX_QUEUE_ENTRIES, ErrorCode::QueueFull);
 
-        msg!("Submitting vote for player {} in game {}", player_index, game_id);
+        if stake > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.match_queue.to_account_info(),
+                    },
+                ),
+                stake,
+            )?;
+        }
+
+        let rating = ctx
+            .accounts
+            .profile
+            .as_ref()
+            .map(|profile| profile.rating)
+            .unwrap_or(DEFAULT_RATING);
+
+        let queue = &mut ctx.accounts.match_queue;
+        queue.bump = ctx.bumps.match_queue;
+        queue.num_players = num_players;
+        queue.stake = stake;
+        queue.entries.push(QueueEntry {
+            player,
+            rating,
+            rating_band,
+            enqueued_at: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("{} queued for {}-player games ({} waiting)", player, num_players, queue.entries.len());
+        Ok(())
+    }
+
+    /// Leave the queue and get the stake back
+    pub fn leave_queue(ctx: Context<LeaveQueue>, num_players: u8, stake: u64) -> Result<()> {
+        let player = ctx.accounts.player.key();
+        let queue = &mut ctx.accounts.match_queue;
+        let index = queue
+            .entries
+            .iter()
+            .position(|e| e.player == player)
+            .ok_or(ErrorCode::NotQueued)?;
+        queue.entries.remove(index);
+
+        if stake > 0 {
+            **queue.to_account_info().try_borrow_mut_lamports()? -= stake;
+            **ctx.accounts.player.try_borrow_mut_lamports()? += stake;
+        }
+
+        msg!("{} left the {}-player queue", player, num_players);
+        Ok(())
+    }
+
+    /// Create a game from the first compatible group of queued players, callable by anyone
+    /// The caller pays for the game and becomes its authority (reveal, close); stakes move into
+    /// the game's wager pot
+    pub fn form_match(
+        ctx: Context<FormMatch>,
+        num_players: u8,
+        stake: u64,
+        nonce: u128,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        require!(
+            num_players >= 2 && num_players <= ctx.accounts.config.max_players,
+            ErrorCode::InvalidPlayerCount
+        );
+
+        let group = ctx
+            .accounts
+            .match_queue
+            .find_match()
+            .ok_or(ErrorCode::NoMatch)?;
+
+        let game_id = ctx.accounts.host_state.allocate_game_id(
+            ctx.bumps.host_state,
+            ctx.accounts.payer.key(),
+        );
+
+        let queue = &mut ctx.accounts.match_queue;
+        let mut players = [Pubkey::default(); 8];
+        // Remove from the back so earlier indices stay valid
+        for (seat, &index) in group.iter().enumerate().rev() {
+            players[seat] = queue.entries.remove(index).player;
+        }
+
+        let pot = stake
+            .checked_mul(num_players as u64)
+            .ok_or(ErrorCode::StakeOverflow)?;
+        if pot > 0 {
+            **queue.to_account_info().try_borrow_mut_lamports()? -= pot;
+            **ctx.accounts.game_account.to_account_info().try_borrow_mut_lamports()? += pot;
+        }
+
+        msg!("Forming {}-player match as game {}", num_players, game_id);
+
+        let game = &mut ctx.accounts.game_account;
+        game.game_id = game_id;
+        game.bump = ctx.bumps.game_account;
+        game.authority = ctx.accounts.payer.key();
+        game.num_players = num_players;
+        game.nonce = nonce;
+        game.players = players;
+        game.failed_computation = NO_FAILED_COMPUTATION;
+        game.pending_computation = NO_PENDING_COMPUTATION;
+        game.status = GameStatus::Lobby as u8;
+        game.wager = stake;
+        game.pot = pot;
+        game.saboteur_index = NO_SABOTEUR;
+        game.voting_period = ctx.accounts.config.voting_period;
+        game.quorum_bps = ctx.accounts.config.quorum_bps;
+        game.matchmade = true;
+        let game_key = game.key();
+        let computation_offset = game.begin_computation(game_key, ComputationKind::InitGame)?;
+
+        let creation_fee = ctx.accounts.config.creation_fee;
+        if creation_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                    },
+                ),
+                creation_fee,
+            )?;
+            ctx.accounts.treasury.total_collected += creation_fee;
+        }
 
         let args = ArgBuilder::new()
-            .x25519_pubkey(vote_encryption_pubkey)
-            .plaintext_u128(vote_nonce)
-            .encrypted_bool(encrypted_vote) // Vote: true = SAFE, false = UNSAFE
-            .plaintext_u128(ctx.accounts.game_account.nonce)
-            .plaintext_u8(player_index)
-            .account(
-                ctx.accounts.game_account.key(),
-                8 + 1, // discriminator + bump
-                32 * 8, // 8 vote slots, 32 bytes each
-            )
+            .plaintext_u128(nonce)
+            .plaintext_u8(num_players)
             .build();
 
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
@@ -135,7 +478,7 @@ pub mod veil {
             computation_offset,
             args,
             None,
-            vec![VoteCallback::callback_ix(
+            vec![InitGameCallback::callback_ix(
                 computation_offset,
                 &ctx.accounts.mxe_account,
                 &[CallbackAccount {
@@ -147,40 +490,135 @@ pub mod veil {
             0,
         )?;
 
+        let slot = Clock::get()?.slot;
+        let authority = ctx.accounts.game_account.authority;
+
+        emit!(GameCreatedEvent {
+            game_id,
+            authority,
+            num_players,
+            slot,
+        });
+
+        emit!(MatchFormedEvent {
+            game_id,
+            authority,
+            stake,
+            players: players[..num_players as usize].to_vec(),
+            slot,
+        });
+
+        if creation_fee > 0 {
+            emit!(FeeCollectedEvent {
+                game_id,
+                authority,
+                kind: FeeKind::Creation as u8,
+                amount: creation_fee,
+                slot,
+            });
+        }
+
+        emit!(ComputationQueuedEvent {
+            game_id,
+            authority,
+            computation_offset,
+            kind: ComputationKind::InitGame as u8,
+            slot,
+        });
+
         Ok(())
     }
 
-    #[arcium_callback(encrypted_ix = "vote")]
-    pub fn vote_callback(
-        ctx: Context<VoteCallback>,
-        output: SignedComputationOutputs<VoteOutput>,
+    // ===== VOTING =====
+
+    /// Submit encrypted vote
+    /// Vote is encrypted client-side using x25519 + Rescue cipher to the MXE
+    /// Each ballot gets its own VoteAccount, so the player can check it on chain and
+    /// concurrent votes never touch each other's data; only the reveal circuit decrypts it
+    pub fn submit_vote(
+        ctx: Context<SubmitVote>,
+        game_id: u32,
+        player_index: u8,
+        encrypted_vote: [u8; 32],
+        vote_encryption_pubkey: [u8; 32],
+        vote_nonce: u128,
     ) -> Result<()> {
-        let o = match output.verify_output(
-            &ctx.accounts.cluster_account,
-            &ctx.accounts.computation_account,
-        ) {
-            Ok(VoteOutput { field_0 }) => field_0,
-            Err(_) => return Err(ErrorCode::AbortedComputation.into()),
-        };
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+        require!(!ctx.accounts.game_account.frozen, ErrorCode::GameFrozen);
+
+        require!(
+            ctx.accounts.game_account.status == GameStatus::Voting as u8,
+            ErrorCode::InvalidGameStatus
+        );
+
+        require!(
+            !ctx.accounts.game_account.voting_closed(Clock::get()?.unix_timestamp),
+            ErrorCode::VotingClosed
+        );
+
+        require!(player_index < ctx.accounts.game_account.num_players, ErrorCode::InvalidPlayerIndex);
+
+        require!(
+            ctx.accounts.game_account.players[player_index as usize] == ctx.accounts.player.key(),
+            ErrorCode::NotSeatOwner
+        );
+
+        require!(
+            ctx.accounts.game_account.tournament == Pubkey::default()
+                || ctx.accounts.game_account.confirmed & (1 << player_index) != 0,
+            ErrorCode::SeatNotConfirmed
+        );
+
+        msg!("Submitting vote for player {} in game {}", player_index, game_id);
+
+        let vote = &mut ctx.accounts.vote_account;
+        vote.bump = ctx.bumps.vote_account;
+        vote.game = ctx.accounts.game_account.key();
+        vote.seat = player_index;
+        vote.player = ctx.accounts.player.key();
+        vote.encryption_pubkey = vote_encryption_pubkey;
+        vote.nonce = vote_nonce;
+        vote.encrypted_vote = encrypted_vote;
+        vote.submitted_at = Clock::get()?.unix_timestamp;
 
-        ctx.accounts.game_account.vote_state = o.ciphertexts;
-        ctx.accounts.game_account.nonce = o.nonce;
-        ctx.accounts.game_account.votes_received += 1;
+        // The vote account is created with `init`, so a seat can only be counted once
+        let game = &mut ctx.accounts.game_account;
+        require!(game.record_vote(player_index), ErrorCode::AlreadyVoted);
 
-        // If all votes in, mark ready for reveal
-        if ctx.accounts.game_account.votes_received == ctx.accounts.game_account.num_players {
-            ctx.accounts.game_account.status = GameStatus::Computing as u8;
+        // If all votes in, mark ready for reveal; otherwise reveal_result may go ahead
+        // on quorum once the deadline passed
+        if game.votes_received == game.num_players {
+            game.status = GameStatus::Computing as u8;
+            game.votes_completed_at = vote.submitted_at;
         }
 
         emit!(VoteSubmittedEvent {
-            game_id: ctx.accounts.game_account.game_id,
-            votes_received: ctx.accounts.game_account.votes_received,
-            total_players: ctx.accounts.game_account.num_players,
+            game_id,
+            authority: game.authority,
+            votes_received: game.votes_received,
+            total_players: game.num_players,
         });
 
         Ok(())
     }
 
+    /// Return a vote account's rent to its voter once the game is finished or closed
+    pub fn close_vote(ctx: Context<CloseVote>, _seat: u8) -> Result<()> {
+        let game = &ctx.accounts.game_account;
+        if !game.data_is_empty() {
+            let data = game.try_borrow_data()?;
+            let game = GameAccount::try_deserialize(&mut &data[..])?;
+            require!(
+                game.status == GameStatus::Finished as u8
+                    || game.abandoned(Clock::get()?.unix_timestamp)
+                    || game.frozen,
+                ErrorCode::InvalidGameStatus
+            );
+        }
+
+        Ok(())
+    }
+
     // ===== REVEAL COMPUTATION DEFINITION =====
     pub fn init_reveal_comp_def(ctx: Context<InitRevealCompDef>) -> Result<()> {
         init_comp_def(ctx.accounts, None, None)?;
@@ -189,36 +627,57 @@ pub mod veil {
 
     /// Reveal game result
     /// MPC computes:
-    /// 1. Decrypt all votes
+    /// 1. Decrypt all ballots; seats without one abstain
     /// 2. Select random saboteur (inside MPC, server never knows until reveal)
-    /// 3. Determine majority vote (excluding saboteur)
+    /// 3. Determine majority vote (excluding saboteur and abstentions)
     /// 4. Check if community won
+    /// Callable by the authority, by anyone for matchmade games, and by anyone once
+    /// REVEAL_TIMEOUT_SECONDS passed since the game could first be revealed
     pub fn reveal_result(
         ctx: Context<RevealResult>,
-        computation_offset: u64,
         game_id: u32,
     ) -> Result<()> {
         require!(
-            ctx.accounts.payer.key() == ctx.accounts.game_account.authority,
+            ctx.accounts
+                .game_account
+                .may_reveal(ctx.accounts.payer.key(), Clock::get()?.unix_timestamp),
             ErrorCode::InvalidAuthority
         );
 
+        require!(!ctx.accounts.game_account.frozen, ErrorCode::GameFrozen);
+
+        // Without every ballot, the reveal may go ahead once voting closed with a quorum;
+        // seats that did not vote abstain
+        let game = &mut ctx.accounts.game_account;
+        if game.status == GameStatus::Voting as u8 {
+            require!(
+                game.voting_closed(Clock::get()?.unix_timestamp),
+                ErrorCode::VotingStillOpen
+            );
+            require!(game.quorum_reached(), ErrorCode::QuorumNotReached);
+            game.status = GameStatus::Computing as u8;
+        }
         require!(
-            ctx.accounts.game_account.status == GameStatus::Computing as u8,
+            game.status == GameStatus::Computing as u8,
             ErrorCode::InvalidGameStatus
         );
 
+        let game_key = ctx.accounts.game_account.key();
+        let computation_offset = ctx
+            .accounts
+            .game_account
+            .begin_computation(game_key, ComputationKind::Reveal)?;
+
+        // remaining_accounts: the VoteAccount of every voted seat in seat order, then the
+        // PlayerProfile PDA of every seat in seat order (existing or not), then for each
+        // season scored the Season and every seat's SeasonEntry PDA; updated by the callback
+        // unless results are private
         msg!("Revealing result for game {}", game_id);
 
-        let args = ArgBuilder::new()
-            .plaintext_u128(ctx.accounts.game_account.nonce)
-            .plaintext_u8(ctx.accounts.game_account.num_players)
-            .account(
-                ctx.accounts.game_account.key(),
-                8 + 1, // discriminator + bump
-                32 * 8, // 8 encrypted votes
-            )
-            .build();
+        let (args, callback_accounts) = ctx
+            .accounts
+            .game_account
+            .reveal_arguments(game_key, ctx.remaining_accounts)?;
 
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
@@ -230,15 +689,20 @@ pub mod veil {
             vec![RevealResultCallback::callback_ix(
                 computation_offset,
                 &ctx.accounts.mxe_account,
-                &[CallbackAccount {
-                    pubkey: ctx.accounts.game_account.key(),
-                    is_writable: true,
-                }],
+                &callback_accounts,
             )?],
             1,
             0,
         )?;
 
+        emit!(ComputationQueuedEvent {
+            game_id,
+            authority: ctx.accounts.game_account.authority,
+            computation_offset,
+            kind: ComputationKind::Reveal as u8,
+            slot: Clock::get()?.slot,
+        });
+
         Ok(())
     }
 
@@ -247,146 +711,1857 @@ pub mod veil {
         ctx: Context<RevealResultCallback>,
         output: SignedComputationOutputs<RevealResultOutput>,
     ) -> Result<()> {
+        ctx.accounts.game_account.end_computation();
+
         let o = match output.verify_output(
             &ctx.accounts.cluster_account,
             &ctx.accounts.computation_account,
         ) {
-            Ok(RevealResultOutput { field_0 }) => field_0,
-            Err(_) => return Err(ErrorCode::AbortedComputation.into()),
+            // A published seed must open the commitment and replay to the reported saboteur
+            Ok(RevealResultOutput { field_0 })
+                if ctx.accounts.game_account.secret_saboteur()
+                    || ctx.accounts.game_account.seed_matches(&field_0.seed, field_0.saboteur_index) =>
+            {
+                field_0
+            }
+            _ => {
+                let clock = Clock::get()?;
+                let game = &mut ctx.accounts.game_account;
+                game.record_failure(ComputationKind::Reveal, clock.unix_timestamp);
+
+                emit!(ComputationAbortedEvent {
+                    game_id: game.game_id,
+                    authority: game.authority,
+                    kind: ComputationKind::Reveal as u8,
+                    retry_count: game.retry_count,
+                    slot: clock.slot,
+                });
+                return Ok(());
+            }
         };
 
+        let num_players = ctx.accounts.game_account.num_players as usize;
+        let private = ctx.accounts.game_account.private_results();
+        // Private games have no per-seat results: nobody is recorded as a winner
+        if !private {
+            ctx.accounts.game_account.winners = o
+                .player_results
+                .iter()
+                .take(num_players)
+                .enumerate()
+                .filter(|(_, won)| **won)
+                .fold(0, |mask, (seat, _)| mask | (1 << seat));
+        }
+        // Each sealed value is a single field element; only its ciphertext is stored
+        let sealed_results: Vec<[u8; 32]> =
+            o.sealed_results.iter().map(|sealed| sealed.ciphertexts[0]).collect();
+        let sealed_roles: Vec<[u8; 32]> =
+            o.sealed_roles.iter().map(|sealed| sealed.ciphertexts[0]).collect();
+        for (seat, sealed) in sealed_results.iter().take(num_players).enumerate() {
+            ctx.accounts.game_account.sealed_results[seat] = *sealed;
+        }
+        for (seat, sealed) in sealed_roles.iter().take(num_players).enumerate() {
+            ctx.accounts.game_account.sealed_roles[seat] = *sealed;
+        }
+        if ctx.accounts.game_account.rules & RULE_HOST_DISCLOSURE != 0 {
+            ctx.accounts.game_account.mxe_saboteur = o.mxe_saboteur.ciphertexts[0];
+            ctx.accounts.game_account.mxe_saboteur_nonce = o.mxe_saboteur.nonce;
+        }
+        if !ctx.accounts.game_account.secret_saboteur() {
+            ctx.accounts.game_account.seed = o.seed;
+            ctx.accounts.game_account.saboteur_index = o.saboteur_index;
+        }
         ctx.accounts.game_account.status = GameStatus::Finished as u8;
+        ctx.accounts.game_account.finished_at = Clock::get()?.unix_timestamp;
+        ctx.accounts.game_account.clear_failure();
+
+        // Ratings and season points would give each seat's result away, and in
+        // secret-saboteur games the saboteur; private games are not scored
+        if !private {
+            let (profiles, seasons) = ctx
+                .remaining_accounts
+                .split_at(num_players.min(ctx.remaining_accounts.len()));
+            record_game_in_profiles(
+                profiles,
+                &ctx.accounts.game_account,
+                o.saboteur_index,
+                &o.player_results,
+                o.community_won,
+            )?;
+            record_game_in_seasons(
+                seasons,
+                &ctx.accounts.game_account,
+                &o.player_results,
+            )?;
+        }
 
+        let saboteur_index = if ctx.accounts.game_account.secret_saboteur() {
+            NO_SABOTEUR
+        } else {
+            o.saboteur_index
+        };
         emit!(GameResultEvent {
             game_id: ctx.accounts.game_account.game_id,
-            saboteur_index: o.saboteur_index,
+            authority: ctx.accounts.game_account.authority,
+            saboteur_index,
             saboteur_voted_safe: o.saboteur_voted_safe,
             community_voted_safe: o.community_voted_safe,
             community_won: o.community_won,
             player_results: o.player_results,
+            rules: ctx.accounts.game_account.rules,
+            sealed_results,
+            sealed_roles,
+            seed: ctx.accounts.game_account.seed,
         });
 
         Ok(())
     }
-}
 
-// ===== ACCOUNT STRUCTURES =====
+    // ===== ABORTED COMPUTATION RETRY =====
 
-#[queue_computation_accounts("init_game", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64, game_id: u32)]
-pub struct CreateGame<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_GAME))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + GameAccount::INIT_SPACE,
-        seeds = [b"game", payer.key().as_ref(), game_id.to_le_bytes().as_ref()],
-        bump,
-    )]
-    pub game_account: Account<'info, GameAccount>,
-}
+    /// Re-queue an aborted init_game computation with a fresh offset
+    /// Authority may retry immediately, anyone else after the cooldown
+    pub fn retry_init_game(
+        ctx: Context<RetryInitGame>,
+        game_id: u32,
+    ) -> Result<()> {
+        ctx.accounts.game_account.begin_retry(
+            ComputationKind::InitGame,
+            ctx.accounts.payer.key(),
+            Clock::get()?.unix_timestamp,
+        )?;
+        let game_key = ctx.accounts.game_account.key();
+        let computation_offset = ctx
+            .accounts
+            .game_account
+            .begin_computation(game_key, ComputationKind::InitGame)?;
 
-#[callback_accounts("init_game")]
-#[derive(Accounts)]
-pub struct InitGameCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_GAME))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    /// CHECK: Checked by Arcium program
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: Sysvar
-    pub instructions_sysvar: AccountInfo<'info>,
-    #[account(mut)]
-    pub game_account: Account<'info, GameAccount>,
-}
+        msg!("Retrying init for game {} (attempt {})", game_id, ctx.accounts.game_account.retry_count);
 
-#[init_computation_definition_accounts("init_game", payer)]
-#[derive(Accounts)]
-pub struct InitGameCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: Checked by Arcium program
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+        let args = ArgBuilder::new()
+            .plaintext_u128(ctx.accounts.game_account.nonce)
+            .plaintext_u8(ctx.accounts.game_account.num_players)
+            .build();
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![InitGameCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[CallbackAccount {
+                    pubkey: ctx.accounts.game_account.key(),
+                    is_writable: true,
+                }],
+            )?],
+            1,
+            0,
+        )?;
+
+        emit!(ComputationQueuedEvent {
+            game_id,
+            authority: ctx.accounts.game_account.authority,
+            computation_offset,
+            kind: ComputationKind::InitGame as u8,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Re-queue an aborted reveal_result computation with a fresh offset
+    /// remaining_accounts: vote accounts, then profile and season accounts, as for reveal_result
+    pub fn retry_reveal_result(
+        ctx: Context<RetryRevealResult>,
+        game_id: u32,
+    ) -> Result<()> {
+        ctx.accounts.game_account.begin_retry(
+            ComputationKind::Reveal,
+            ctx.accounts.payer.key(),
+            Clock::get()?.unix_timestamp,
+        )?;
+        let game_key = ctx.accounts.game_account.key();
+        let computation_offset = ctx
+            .accounts
+            .game_account
+            .begin_computation(game_key, ComputationKind::Reveal)?;
+
+        msg!("Retrying reveal for game {} (attempt {})", game_id, ctx.accounts.game_account.retry_count);
+
+        let (args, callback_accounts) = ctx
+            .accounts
+            .game_account
+            .reveal_arguments(game_key, ctx.remaining_accounts)?;
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![RevealResultCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &callback_accounts,
+            )?],
+            1,
+            0,
+        )?;
+
+        emit!(ComputationQueuedEvent {
+            game_id,
+            authority: ctx.accounts.game_account.authority,
+            computation_offset,
+            kind: ComputationKind::Reveal as u8,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    // ===== SABOTEUR DISCLOSURE =====
+
+    pub fn init_disclose_comp_def(ctx: Context<InitDiscloseCompDef>) -> Result<()> {
+        init_comp_def(ctx.accounts, None, None)?;
+        Ok(())
+    }
+
+    /// Seal the secret saboteur of a finished game to the host's x25519 key
+    /// Only for RULE_HOST_DISCLOSURE games, once disclosure_delay has passed since the reveal
+    /// The host opens host_sealed_saboteur with its key and nonce
+    pub fn disclose_saboteur(
+        ctx: Context<DiscloseSaboteur>,
+        game_id: u32,
+        host_encryption_pubkey: [u8; 32],
+        nonce: u128,
+    ) -> Result<()> {
+        let game = &mut ctx.accounts.game_account;
+        require!(game.rules & RULE_HOST_DISCLOSURE != 0, ErrorCode::DisclosureNotAllowed);
+        require!(
+            game.status == GameStatus::Finished as u8,
+            ErrorCode::InvalidGameStatus
+        );
+        require!(!game.disclosed, ErrorCode::AlreadyDisclosed);
+        // check_rules bounds disclosure_delay, so this cannot overflow
+        require!(
+            Clock::get()?.unix_timestamp >= game.finished_at + game.disclosure_delay,
+            ErrorCode::DisclosureTooEarly
+        );
+
+        let game_key = game.key();
+        let computation_offset = game.begin_computation(game_key, ComputationKind::Disclose)?;
+        game.disclosure_nonce = nonce;
+
+        msg!("Disclosing saboteur of game {} to its host", game_id);
+
+        let args = ArgBuilder::new()
+            .x25519_pubkey(host_encryption_pubkey)
+            .plaintext_u128(nonce)
+            .plaintext_u128(game.mxe_saboteur_nonce)
+            .encrypted_u8(game.mxe_saboteur)
+            .build();
+
+        ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+        queue_computation(
+            ctx.accounts,
+            computation_offset,
+            args,
+            None,
+            vec![DiscloseSaboteurCallback::callback_ix(
+                computation_offset,
+                &ctx.accounts.mxe_account,
+                &[CallbackAccount {
+                    pubkey: game_key,
+                    is_writable: true,
+                }],
+            )?],
+            1,
+            0,
+        )?;
+
+        emit!(ComputationQueuedEvent {
+            game_id,
+            authority: ctx.accounts.game_account.authority,
+            computation_offset,
+            kind: ComputationKind::Disclose as u8,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    #[arcium_callback(encrypted_ix = "disclose_saboteur")]
+    pub fn disclose_saboteur_callback(
+        ctx: Context<DiscloseSaboteurCallback>,
+        output: SignedComputationOutputs<DiscloseSaboteurOutput>,
+    ) -> Result<()> {
+        ctx.accounts.game_account.end_computation();
+
+        let o = match output.verify_output(
+            &ctx.accounts.cluster_account,
+            &ctx.accounts.computation_account,
+        ) {
+            Ok(DiscloseSaboteurOutput { field_0 }) => field_0,
+            Err(_) => {
+                // Nothing else waits on a disclosure, the host simply discloses again
+                let game = &ctx.accounts.game_account;
+                emit!(ComputationAbortedEvent {
+                    game_id: game.game_id,
+                    authority: game.authority,
+                    kind: ComputationKind::Disclose as u8,
+                    retry_count: game.retry_count,
+                    slot: Clock::get()?.slot,
+                });
+                return Ok(());
+            }
+        };
+
+        let game = &mut ctx.accounts.game_account;
+        game.host_sealed_saboteur = o.sealed_saboteur.ciphertexts[0];
+        game.disclosed = true;
+
+        emit!(SaboteurDisclosedEvent {
+            game_id: game.game_id,
+            authority: game.authority,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    // ===== PLAYER PROFILES =====
+
+    /// Create the signer's profile; games finished after this count towards its stats
+    pub fn create_profile(ctx: Context<CreateProfile>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.bump = ctx.bumps.profile;
+        profile.player = ctx.accounts.player.key();
+        profile.rating = DEFAULT_RATING;
+
+        msg!("Created profile for {}", profile.player);
+        Ok(())
+    }
+
+    // ===== SEASONS =====
+
+    pub fn create_season(
+        ctx: Context<CreateSeason>,
+        season_id: u32,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        require!(end_ts > start_ts, ErrorCode::InvalidSeason);
+
+        let season = &mut ctx.accounts.season;
+        season.bump = ctx.bumps.season;
+        season.season_id = season_id;
+        season.start_ts = start_ts;
+        season.end_ts = end_ts;
+
+        msg!("Created season {} ({} - {})", season_id, start_ts, end_ts);
+        Ok(())
+    }
+
+    /// Enter the signer in a season; games finished inside its window add to the entry's score
+    pub fn join_season(ctx: Context<JoinSeason>, season_id: u32) -> Result<()> {
+        let season = &mut ctx.accounts.season;
+        require!(Clock::get()?.unix_timestamp < season.end_ts, ErrorCode::SeasonEnded);
+        season.entry_count += 1;
+
+        let entry = &mut ctx.accounts.entry;
+        entry.bump = ctx.bumps.entry;
+        entry.season_id = season_id;
+        entry.player = ctx.accounts.player.key();
+        entry.start_ts = season.start_ts;
+        entry.end_ts = season.end_ts;
+
+        msg!("{} joined season {}", entry.player, season_id);
+        Ok(())
+    }
+
+    /// Rank a batch of a finished season's entries into its SeasonResult, callable by anyone
+    /// The season closes once every entry has been ranked
+    /// remaining_accounts: unranked SeasonEntry accounts of this season, writable
+    pub fn close_season(ctx: Context<CloseSeason>, season_id: u32) -> Result<()> {
+        let season = &mut ctx.accounts.season;
+        require!(!season.closed, ErrorCode::SeasonClosed);
+        require!(
+            Clock::get()?.unix_timestamp >= season.end_ts,
+            ErrorCode::SeasonNotEnded
+        );
+
+        let result = &mut ctx.accounts.season_result;
+        result.bump = ctx.bumps.season_result;
+        result.season_id = season_id;
+
+        for info in ctx.remaining_accounts {
+            require!(
+                info.owner == &crate::ID && info.is_writable,
+                ErrorCode::InvalidSeasonEntry
+            );
+            let mut entry = SeasonEntry::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(
+                entry.season_id == season_id && !entry.ranked,
+                ErrorCode::InvalidSeasonEntry
+            );
+
+            entry.ranked = true;
+            let mut data = info.try_borrow_mut_data()?;
+            entry.try_serialize(&mut &mut data[..])?;
+
+            result.insert(SeasonStanding {
+                player: entry.player,
+                score: entry.score,
+                wins: entry.wins,
+                games_played: entry.games_played,
+            });
+            season.ranked_entries += 1;
+        }
+
+        msg!("Season {}: ranked {}/{}", season_id, season.ranked_entries, season.entry_count);
+
+        if season.ranked_entries == season.entry_count {
+            season.closed = true;
+
+            emit!(SeasonClosedEvent {
+                season_id,
+                entry_count: season.entry_count,
+                winner: result.standings.first().map(|s| s.player).unwrap_or_default(),
+                slot: Clock::get()?.slot,
+            });
+        }
+
+        Ok(())
+    }
+
+    // ===== TOURNAMENTS =====
+
+    /// Open registration for a tournament; the organizer may seed the prize pool
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u32,
+        entry_fee: u64,
+        max_entrants: u8,
+        prize_contribution: u64,
+    ) -> Result<()> {
+        require!(
+            max_entrants >= 2 && max_entrants as usize <= MAX_TOURNAMENT_ENTRANTS,
+            ErrorCode::InvalidTournament
+        );
+
+        if prize_contribution > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.organizer.to_account_info(),
+                        to: ctx.accounts.tournament.to_account_info(),
+                    },
+                ),
+                prize_contribution,
+            )?;
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.bump = ctx.bumps.tournament;
+        tournament.tournament_id = tournament_id;
+        tournament.organizer = ctx.accounts.organizer.key();
+        tournament.entry_fee = entry_fee;
+        tournament.max_entrants = max_entrants;
+        tournament.prize_pool = prize_contribution;
+        tournament.status = TournamentStatus::Registration as u8;
+
+        msg!("Created tournament {} for up to {} players", tournament_id, max_entrants);
+        Ok(())
+    }
+
+    /// Enter the signer, paying the entry fee into the prize pool
+    pub fn register_tournament(ctx: Context<RegisterTournament>, tournament_id: u32) -> Result<()> {
+        let player = ctx.accounts.player.key();
+        let tournament = &ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Registration as u8,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(
+            tournament.roster.len() < tournament.max_entrants as usize,
+            ErrorCode::TournamentFull
+        );
+        require!(!tournament.roster.contains(&player), ErrorCode::AlreadyJoined);
+
+        let entry_fee = tournament.entry_fee;
+        if entry_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.tournament.to_account_info(),
+                    },
+                ),
+                entry_fee,
+            )?;
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.roster.push(player);
+        tournament.prize_pool += entry_fee;
+
+        msg!("{} registered for tournament {}", player, tournament_id);
+        Ok(())
+    }
+
+    pub fn start_tournament(ctx: Context<ManageTournament>, tournament_id: u32) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Registration as u8,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(tournament.roster.len() >= 2, ErrorCode::InvalidPlayerCount);
+
+        tournament.status = TournamentStatus::Running as u8;
+        tournament.round = 1;
+        tournament.round_started_at = Clock::get()?.unix_timestamp;
+
+        msg!("Tournament {} started with {} players", tournament_id, tournament.roster.len());
+        Ok(())
+    }
+
+    /// Seat the next num_players unseated players of the round into an empty game of the organizer
+    /// A single player left over when the round's games are full gets a bye
+    /// Seated players must confirm_tournament_seat before they can vote
+    pub fn seat_tournament_game(
+        ctx: Context<SeatTournamentGame>,
+        tournament_id: u32,
+        game_id: u32,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let game = &mut ctx.accounts.game_account;
+        require!(
+            tournament.status == TournamentStatus::Running as u8,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(
+            game.status == GameStatus::Lobby as u8 || game.status == GameStatus::Voting as u8,
+            ErrorCode::InvalidGameStatus
+        );
+        require!(
+            game.tournament == Pubkey::default()
+                && game.players.iter().all(|p| *p == Pubkey::default()),
+            ErrorCode::TournamentGame
+        );
+        require!(!game.private_results(), ErrorCode::PrivateResultsUnsupported);
+        require!(
+            tournament.games.len() < MAX_TOURNAMENT_GAMES,
+            ErrorCode::TournamentFull
+        );
+
+        let start = tournament.seated as usize;
+        let end = start + game.num_players as usize;
+        require!(end <= tournament.roster.len(), ErrorCode::InvalidPlayerCount);
+
+        for (seat, player) in tournament.roster[start..end].iter().enumerate() {
+            game.players[seat] = *player;
+        }
+        game.tournament = tournament.key();
+        game.confirmed = 0;
+        tournament.games.push(game.key());
+        tournament.seated = end as u8;
+
+        msg!(
+            "Seated players {}-{} of tournament {} round {} in game {}",
+            start,
+            end - 1,
+            tournament_id,
+            tournament.round,
+            game_id
+        );
+        Ok(())
+    }
+
+    /// Accept the seat the organizer gave the signer in a tournament game
+    pub fn confirm_tournament_seat(
+        ctx: Context<JoinGame>,
+        game_id: u32,
+        player_index: u8,
+    ) -> Result<()> {
+        let game = &mut ctx.accounts.game_account;
+        require!(game.tournament != Pubkey::default(), ErrorCode::InvalidGameStatus);
+        require!(player_index < game.num_players, ErrorCode::InvalidPlayerIndex);
+        require!(
+            game.players[player_index as usize] == ctx.accounts.player.key(),
+            ErrorCode::NotSeatOwner
+        );
+        require!(game.confirmed & (1 << player_index) == 0, ErrorCode::SeatAlreadyConfirmed);
+
+        game.confirmed |= 1 << player_index;
+
+        msg!("Player {} confirmed seat {} in game {}", ctx.accounts.player.key(), player_index, game_id);
+        Ok(())
+    }
+
+    /// Move the winners of every finished game of the round on to the next round, callable by anyone
+    /// Frozen games and games whose computation failed for good advance nobody
+    /// Once TOURNAMENT_ROUND_TIMEOUT passed since the round started, the round can advance with
+    /// players left unseated, who move on, and with unfinished games, whose players who confirmed
+    /// their seat move on and the others forfeit
+    /// The tournament finishes when at most one player is left, nobody was eliminated, or after
+    /// MAX_TOURNAMENT_ROUNDS; the remaining players are the champions
+    /// remaining_accounts: the round's games in seating order, writable
+    pub fn advance_tournament(ctx: Context<AdvanceTournament>, tournament_id: u32) -> Result<()> {
+        let tournament_key = ctx.accounts.tournament.key();
+        let tournament = &mut ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Running as u8,
+            ErrorCode::InvalidTournamentStatus
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let timed_out = now >= tournament.round_started_at.saturating_add(TOURNAMENT_ROUND_TIMEOUT);
+        // Everyone is seated, apart from at most one bye
+        require!(
+            timed_out
+                || (!tournament.games.is_empty()
+                    && tournament.roster.len() - tournament.seated as usize <= 1),
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(
+            ctx.remaining_accounts.len() == tournament.games.len(),
+            ErrorCode::TournamentGameMismatch
+        );
+
+        let mut next_roster = Vec::new();
+        for (info, key) in ctx.remaining_accounts.iter().zip(tournament.games.iter()) {
+            require!(
+                info.key() == *key && info.owner == &crate::ID && info.is_writable,
+                ErrorCode::TournamentGameMismatch
+            );
+            let mut game = GameAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(game.tournament == tournament_key, ErrorCode::TournamentGameMismatch);
+
+            if game.status == GameStatus::Finished as u8 {
+                for seat in 0..game.num_players as usize {
+                    if game.winners & (1 << seat) != 0 {
+                        next_roster.push(game.players[seat]);
+                    }
+                }
+            } else if !game.frozen && !game.abandoned(now) {
+                require!(timed_out, ErrorCode::InvalidGameStatus);
+                for seat in 0..game.num_players as usize {
+                    if game.confirmed & (1 << seat) != 0 {
+                        next_roster.push(game.players[seat]);
+                    }
+                }
+            }
+
+            // Release the game so its authority can close it
+            game.tournament = Pubkey::default();
+            let mut data = info.try_borrow_mut_data()?;
+            game.try_serialize(&mut &mut data[..])?;
+        }
+        let seated = tournament.seated as usize;
+        next_roster.extend_from_slice(&tournament.roster[seated..]);
+
+        let eliminated = next_roster.len() < tournament.roster.len();
+        let finished = next_roster.len() <= 1
+            || !eliminated
+            || tournament.round >= MAX_TOURNAMENT_ROUNDS;
+
+        // If every game was abandoned, the whole round shares the prize
+        if !next_roster.is_empty() {
+            tournament.roster = next_roster;
+        }
+        tournament.games.clear();
+        tournament.seated = 0;
+        if finished {
+            tournament.status = TournamentStatus::Finished as u8;
+        } else {
+            tournament.round += 1;
+            tournament.round_started_at = now;
+        }
+
+        msg!(
+            "Tournament {}: {} players left after round {}",
+            tournament_id,
+            tournament.roster.len(),
+            tournament.round
+        );
+
+        emit!(TournamentAdvancedEvent {
+            tournament_id,
+            organizer: tournament.organizer,
+            round: tournament.round,
+            remaining_players: tournament.roster.len() as u8,
+            finished,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Split the prize pool of a finished tournament between its champions, callable by anyone
+    /// The protocol fee and rounding dust go to the treasury
+    /// remaining_accounts: the champions (final roster) in order, writable
+    pub fn distribute_tournament_prizes(
+        ctx: Context<DistributeTournamentPrizes>,
+        tournament_id: u32,
+    ) -> Result<()> {
+        let tournament = &ctx.accounts.tournament;
+        require!(
+            tournament.status == TournamentStatus::Finished as u8,
+            ErrorCode::InvalidTournamentStatus
+        );
+        require!(tournament.prize_pool > 0, ErrorCode::NothingToSettle);
+        require!(
+            ctx.remaining_accounts.len() == tournament.roster.len(),
+            ErrorCode::SeatAccountsMismatch
+        );
+        for (account, champion) in ctx.remaining_accounts.iter().zip(tournament.roster.iter()) {
+            require!(
+                account.key() == *champion && account.is_writable,
+                ErrorCode::SeatAccountsMismatch
+            );
+        }
+
+        let pool = tournament.prize_pool;
+        let protocol_fee =
+            (pool as u128 * ctx.accounts.config.protocol_fee_bps as u128 / BPS_DENOMINATOR) as u64;
+        let champions = tournament.roster.len() as u64;
+        let payout = (pool - protocol_fee) / champions;
+        let to_treasury = pool - payout * champions;
+
+        let tournament_info = ctx.accounts.tournament.to_account_info();
+        for account in ctx.remaining_accounts {
+            **tournament_info.try_borrow_mut_lamports()? -= payout;
+            **account.try_borrow_mut_lamports()? += payout;
+        }
+        if to_treasury > 0 {
+            **tournament_info.try_borrow_mut_lamports()? -= to_treasury;
+            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += to_treasury;
+            ctx.accounts.treasury.total_collected += to_treasury;
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.prize_pool = 0;
+
+        msg!("Tournament {}: paid {} lamports to {} champions", tournament_id, payout, champions);
+
+        let slot = Clock::get()?.slot;
+
+        if to_treasury > 0 {
+            emit!(FeeCollectedEvent {
+                game_id: tournament_id,
+                authority: tournament.organizer,
+                kind: FeeKind::Tournament as u8,
+                amount: to_treasury,
+                slot,
+            });
+        }
+
+        emit!(TournamentPrizesDistributedEvent {
+            tournament_id,
+            organizer: tournament.organizer,
+            prize_pool: pool,
+            protocol_fee: to_treasury,
+            payout,
+            champions: champions as u8,
+            slot,
+        });
+
+        Ok(())
+    }
+
+    // ===== WAGER SETTLEMENT =====
+
+    /// Pay out a game's wager pot, callable by anyone
+    /// Finished games pay the winners after the protocol fee; frozen games and games whose
+    /// computation failed past the retry limit refund every seat in full
+    /// remaining_accounts: the seat owners of all taken seats, in seat order, writable
+    pub fn settle_game<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleGame<'info>>,
+        game_id: u32,
+    ) -> Result<()> {
+        let game = &ctx.accounts.game_account;
+        require!(game.pot > 0, ErrorCode::NothingToSettle);
+
+        let refund = game.frozen || game.abandoned(Clock::get()?.unix_timestamp);
+        require!(
+            refund || game.status == GameStatus::Finished as u8,
+            ErrorCode::InvalidGameStatus
+        );
+
+        let seats: Vec<usize> = (0..game.num_players as usize)
+            .filter(|&seat| game.players[seat] != Pubkey::default())
+            .collect();
+        require!(
+            ctx.remaining_accounts.len() == seats.len(),
+            ErrorCode::SeatAccountsMismatch
+        );
+        for (account, &seat) in ctx.remaining_accounts.iter().zip(&seats) {
+            require!(
+                account.key() == game.players[seat] && account.is_writable,
+                ErrorCode::SeatAccountsMismatch
+            );
+        }
+
+        let pot = game.pot;
+        let protocol_fee = if refund {
+            0
+        } else {
+            (pot as u128 * ctx.accounts.config.protocol_fee_bps as u128 / BPS_DENOMINATOR) as u64
+        };
+
+        let winner_mask = if refund { 0 } else { game.winners };
+        let mut recipients: Vec<usize> = (0..seats.len())
+            .filter(|&i| winner_mask & (1 << seats[i]) != 0)
+            .collect();
+        // Refund, or nobody won: split evenly across every seat
+        if recipients.is_empty() {
+            recipients = (0..seats.len()).collect();
+        }
+        let payout = (pot - protocol_fee) / recipients.len() as u64;
+        // Rounding dust goes to the treasury with the fee
+        let to_treasury = pot - payout * recipients.len() as u64;
+
+        let game_info = ctx.accounts.game_account.to_account_info();
+        for &i in &recipients {
+            **game_info.try_borrow_mut_lamports()? -= payout;
+            **ctx.remaining_accounts[i].try_borrow_mut_lamports()? += payout;
+        }
+        if to_treasury > 0 {
+            **game_info.try_borrow_mut_lamports()? -= to_treasury;
+            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += to_treasury;
+            ctx.accounts.treasury.total_collected += to_treasury;
+        }
+
+        let paid_mask = recipients.iter().fold(0u8, |mask, &i| mask | (1 << seats[i]));
+        let game = &mut ctx.accounts.game_account;
+        game.pot = 0;
+
+        msg!("Settled game {}: {} lamports to {} seats", game_id, payout, recipients.len());
+
+        let slot = Clock::get()?.slot;
+
+        if to_treasury > 0 {
+            emit!(FeeCollectedEvent {
+                game_id,
+                authority: game.authority,
+                kind: FeeKind::Pot as u8,
+                amount: to_treasury,
+                slot,
+            });
+        }
+
+        emit!(GameSettledEvent {
+            game_id,
+            authority: game.authority,
+            pot,
+            protocol_fee: to_treasury,
+            payout,
+            paid_seats: paid_mask,
+            refunded: refund,
+            slot,
+        });
+
+        Ok(())
+    }
+
+    /// Close a finished game and return its rent to the authority
+    /// Games whose computation failed past the retry limit, or frozen games, can be closed as well
+    /// Any wager pot must be settled first
+    pub fn close_game(ctx: Context<CloseGame>, game_id: u32) -> Result<()> {
+        let game = &ctx.accounts.game_account;
+
+        require!(
+            game.status == GameStatus::Finished as u8
+                || game.abandoned(Clock::get()?.unix_timestamp)
+                || game.frozen,
+            ErrorCode::InvalidGameStatus
+        );
+        require!(game.pot == 0, ErrorCode::PotNotSettled);
+        // Released by advance_tournament once the round is over
+        require!(game.tournament == Pubkey::default(), ErrorCode::TournamentGame);
+
+        // The room code is released together with the game
+        require!(
+            game.room_code.is_empty() || ctx.accounts.room_index.is_some(),
+            ErrorCode::RoomIndexRequired
+        );
+
+        msg!("Closing game {}", game_id);
+
+        emit!(GameClosedEvent {
+            game_id,
+            authority: ctx.accounts.authority.key(),
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    // ===== PROGRAM CONFIG =====
+
+    /// Create the global config, once, signed by the program's upgrade authority
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        params.validate()?;
+
+        let config = &mut ctx.accounts.config;
+        config.bump = ctx.bumps.config;
+        config.admin = ctx.accounts.admin.key();
+        config.apply(&params);
+
+        ctx.accounts.treasury.bump = ctx.bumps.treasury;
+
+        msg!("Config initialized, admin {}", config.admin);
+        Ok(())
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        params.validate()?;
+        ctx.accounts.config.apply(&params);
+
+        msg!("Config updated, paused: {}", params.paused);
+        Ok(())
+    }
+
+    pub fn transfer_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        require!(new_admin != Pubkey::default(), ErrorCode::InvalidAuthority);
+        ctx.accounts.config.admin = new_admin;
+
+        msg!("Config admin transferred to {}", new_admin);
+        Ok(())
+    }
+
+    /// Move collected fees out of the treasury, keeping it rent-exempt
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        let rent_exempt = Rent::get()?.minimum_balance(treasury_info.data_len());
+        require!(
+            treasury_info.lamports().saturating_sub(rent_exempt) >= amount,
+            ErrorCode::InsufficientTreasury
+        );
+
+        **treasury_info.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += amount;
+        ctx.accounts.treasury.total_withdrawn += amount;
+
+        msg!("Withdrew {} lamports from treasury to {}", amount, ctx.accounts.recipient.key());
+
+        emit!(TreasuryWithdrawnEvent {
+            admin: ctx.accounts.admin.key(),
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    // ===== EMERGENCY CONTROLS =====
+
+    /// Block new games and votes program-wide
+    pub fn pause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = true;
+        msg!("Program paused");
+        Ok(())
+    }
+
+    pub fn unpause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.config.paused = false;
+        msg!("Program unpaused");
+        Ok(())
+    }
+
+    /// Halt a disputed game: joins, votes, reveal and retries are rejected until unfrozen
+    /// The authority can close a frozen game to recover its rent
+    pub fn freeze_game(ctx: Context<FreezeGame>, game_id: u32) -> Result<()> {
+        let game = &mut ctx.accounts.game_account;
+        require!(!game.frozen, ErrorCode::GameFrozen);
+        game.frozen = true;
+
+        msg!("Game {} frozen", game_id);
+
+        emit!(GameFrozenEvent {
+            game_id,
+            authority: game.authority,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    pub fn unfreeze_game(ctx: Context<FreezeGame>, game_id: u32) -> Result<()> {
+        let game = &mut ctx.accounts.game_account;
+        require!(game.frozen, ErrorCode::GameNotFrozen);
+        game.frozen = false;
+
+        msg!("Game {} unfrozen", game_id);
+
+        emit!(GameUnfrozenEvent {
+            game_id,
+            authority: game.authority,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+}
+
+// ===== ACCOUNT STRUCTURES =====
+
+#[queue_computation_accounts("init_game", payer)]
+#[derive(Accounts)]
+pub struct CreateGame<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(&game_account.key(), 0),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: Checked by Arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_GAME))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + HostState::INIT_SPACE,
+        seeds = [b"host", payer.key().as_ref()],
+        bump,
+    )]
+    pub host_state: Account<'info, HostState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GameAccount::INIT_SPACE,
+        seeds = [b"game", payer.key().as_ref(), host_state.next_game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game_account: Account<'info, GameAccount>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[callback_accounts("init_game")]
+#[derive(Accounts)]
+pub struct InitGameCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_GAME))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        address = derive_comp_pda!(game_account.pending_offset, mxe_account, ErrorCode::ClusterNotSet)
+            @ ErrorCode::UnexpectedComputation
+    )]
+    /// CHECK: Checked by Arcium program, must be the computation the game is waiting for
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_account.authority.as_ref(), game_account.game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        constraint = game_account.pending_computation == ComputationKind::InitGame as u8
+            @ ErrorCode::UnexpectedComputation
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[init_computation_definition_accounts("init_game", payer)]
+#[derive(Accounts)]
+pub struct InitGameCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Checked by Arcium program
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u32)]
+pub struct JoinGame<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(address = game_account.authority)]
+    /// CHECK: Game authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority
+    )]
+    pub game_account: Account<'info, GameAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u32, room_code: String)]
+pub struct RegisterRoom<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority
+    )]
+    pub game_account: Account<'info, GameAccount>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RoomIndex::INIT_SPACE,
+        seeds = [b"room", room_code.as_bytes()],
+        bump,
+    )]
+    pub room_index: Account<'info, RoomIndex>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(num_players: u8, stake: u64)]
+pub struct EnqueuePlayer<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + MatchQueue::INIT_SPACE,
+        seeds = [b"match_queue", [num_players].as_ref(), stake.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+    #[account(seeds = [b"profile", player.key().as_ref()], bump = profile.bump)]
+    pub profile: Option<Account<'info, PlayerProfile>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(num_players: u8, stake: u64)]
+pub struct LeaveQueue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"match_queue", [num_players].as_ref(), stake.to_le_bytes().as_ref()],
+        bump = match_queue.bump
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+}
+
+#[queue_computation_accounts("init_game", payer)]
+#[derive(Accounts)]
+#[instruction(num_players: u8, stake: u64)]
+pub struct FormMatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(&game_account.key(), 0),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: Checked by Arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_GAME))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + HostState::INIT_SPACE,
+        seeds = [b"host", payer.key().as_ref()],
+        bump,
+    )]
+    pub host_state: Account<'info, HostState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GameAccount::INIT_SPACE,
+        seeds = [b"game", payer.key().as_ref(), host_state.next_game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game_account: Account<'info, GameAccount>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(
+        mut,
+        seeds = [b"match_queue", [num_players].as_ref(), stake.to_le_bytes().as_ref()],
+        bump = match_queue.bump
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u32, player_index: u8)]
+pub struct SubmitVote<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(address = game_account.authority)]
+    /// CHECK: Game authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority
+    )]
+    pub game_account: Account<'info, GameAccount>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + VoteAccount::INIT_SPACE,
+        seeds = [b"vote", game_account.key().as_ref(), [player_index].as_ref()],
+        bump,
+    )]
+    pub vote_account: Account<'info, VoteAccount>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(seat: u8)]
+pub struct CloseVote<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// CHECK: The game the vote belongs to, may already be closed
+    pub game_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"vote", game_account.key().as_ref(), [seat].as_ref()],
+        bump = vote_account.bump,
+        has_one = player,
+        close = player
+    )]
+    pub vote_account: Account<'info, VoteAccount>,
+}
+
+#[queue_computation_accounts("reveal_result", payer)]
+#[derive(Accounts)]
+#[instruction(game_id: u32)]
+pub struct RevealResult<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(&game_account.key(), game_account.computation_count),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: Checked by Arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = game_account.authority)]
+    /// CHECK: Game authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[callback_accounts("reveal_result")]
+#[derive(Accounts)]
+pub struct RevealResultCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        address = derive_comp_pda!(game_account.pending_offset, mxe_account, ErrorCode::ClusterNotSet)
+            @ ErrorCode::UnexpectedComputation
+    )]
+    /// CHECK: Checked by Arcium program, must be the computation the game is waiting for
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_account.authority.as_ref(), game_account.game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        constraint = game_account.pending_computation == ComputationKind::Reveal as u8
+            @ ErrorCode::UnexpectedComputation
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[init_computation_definition_accounts("reveal_result", payer)]
+#[derive(Accounts)]
+pub struct InitRevealCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Checked by Arcium program
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[queue_computation_accounts("init_game", payer)]
+#[derive(Accounts)]
+#[instruction(game_id: u32)]
+pub struct RetryInitGame<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(&game_account.key(), game_account.computation_count),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: Checked by Arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_INIT_GAME))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = game_account.authority)]
+    /// CHECK: Game authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[queue_computation_accounts("reveal_result", payer)]
+#[derive(Accounts)]
+#[instruction(game_id: u32)]
+pub struct RetryRevealResult<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(&game_account.key(), game_account.computation_count),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: Checked by Arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = game_account.authority)]
+    /// CHECK: Game authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[queue_computation_accounts("disclose_saboteur", payer)]
+#[derive(Accounts)]
+#[instruction(game_id: u32)]
+pub struct DiscloseSaboteur<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    /// CHECK: Checked by Arcium program
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(
+            derive_computation_offset(&game_account.key(), game_account.computation_count),
+            mxe_account,
+            ErrorCode::ClusterNotSet
+        )
+    )]
+    /// CHECK: Checked by Arcium program
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_DISCLOSE))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        mut,
+        seeds = [b"game", payer.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[callback_accounts("disclose_saboteur")]
+#[derive(Accounts)]
+pub struct DiscloseSaboteurCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_DISCLOSE))]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = derive_mxe_pda!())]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        address = derive_comp_pda!(game_account.pending_offset, mxe_account, ErrorCode::ClusterNotSet)
+            @ ErrorCode::UnexpectedComputation
+    )]
+    /// CHECK: Checked by Arcium program, must be the computation the game is waiting for
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: Sysvar
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", game_account.authority.as_ref(), game_account.game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        constraint = game_account.pending_computation == ComputationKind::Disclose as u8
+            @ ErrorCode::UnexpectedComputation
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[init_computation_definition_accounts("disclose_saboteur", payer)]
+#[derive(Accounts)]
+pub struct InitDiscloseCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, address = derive_mxe_pda!())]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: Checked by Arcium program
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u32)]
+pub struct CloseGame<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority,
+        close = authority
+    )]
+    pub game_account: Account<'info, GameAccount>,
+    #[account(
+        mut,
+        seeds = [b"room", game_account.room_code.as_bytes()],
+        bump = room_index.bump,
+        constraint = room_index.game == game_account.key() @ ErrorCode::RoomIndexMismatch,
+        close = authority
+    )]
+    pub room_index: Option<Account<'info, RoomIndex>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Treasury::INIT_SPACE,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::Veil>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::InvalidAuthority)]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::InvalidAuthority
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [b"profile", player.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    pub system_program: Program<'info, System>,
 }
 
-#[queue_computation_accounts("vote", payer)]
 #[derive(Accounts)]
-#[instruction(computation_offset: u64, game_id: u32, player_index: u8)]
-pub struct SubmitVote<'info> {
+#[instruction(season_id: u32)]
+pub struct CreateSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ ErrorCode::InvalidAuthority)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Season::INIT_SPACE,
+        seeds = [b"season", season_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Account<'info, Season>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct JoinSeason<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"season", season_id.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
+    #[account(
+        init,
+        payer = player,
+        space = 8 + SeasonEntry::INIT_SPACE,
+        seeds = [b"season_entry", season_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub entry: Account<'info, SeasonEntry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct CloseSeason<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"season", season_id.to_le_bytes().as_ref()],
+        bump = season.bump
+    )]
+    pub season: Account<'info, Season>,
     #[account(
         init_if_needed,
-        space = 9,
         payer = payer,
-        seeds = [&SIGN_PDA_SEED],
+        space = 8 + SeasonResult::INIT_SPACE,
+        seeds = [b"season_result", season_id.to_le_bytes().as_ref()],
         bump,
-        address = derive_sign_pda!(),
     )]
-    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
-    pub clock_account: Account<'info, ClockAccount>,
+    pub season_result: Account<'info, SeasonResult>,
     pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u32)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+    #[account(
+        init,
+        payer = organizer,
+        space = 8 + Tournament::INIT_SPACE,
+        seeds = [b"tournament", organizer.key().as_ref(), tournament_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u32)]
+pub struct RegisterTournament<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(address = tournament.organizer)]
+    /// CHECK: Tournament organizer
+    pub organizer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", organizer.key().as_ref(), tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u32)]
+pub struct ManageTournament<'info> {
+    pub organizer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", organizer.key().as_ref(), tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+        has_one = organizer
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u32, game_id: u32)]
+pub struct SeatTournamentGame<'info> {
+    pub organizer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", organizer.key().as_ref(), tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+        has_one = organizer
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [b"game", organizer.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u32)]
+pub struct AdvanceTournament<'info> {
+    #[account(address = tournament.organizer)]
+    /// CHECK: Tournament organizer
+    pub organizer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", organizer.key().as_ref(), tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u32)]
+pub struct DistributeTournamentPrizes<'info> {
+    #[account(address = tournament.organizer)]
+    /// CHECK: Tournament organizer
+    pub organizer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"tournament", organizer.key().as_ref(), tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ ErrorCode::InvalidAuthority)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    /// CHECK: Any account may receive the withdrawal
+    pub recipient: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u32)]
+pub struct SettleGame<'info> {
     #[account(address = game_account.authority)]
     /// CHECK: Game authority
     pub authority: UncheckedAccount<'info>,
@@ -397,131 +2572,800 @@ pub struct SubmitVote<'info> {
         has_one = authority
     )]
     pub game_account: Account<'info, GameAccount>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"treasury"], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
 }
 
-#[callback_accounts("vote")]
 #[derive(Accounts)]
-pub struct VoteCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_VOTE))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    /// CHECK: Checked by Arcium program
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: Sysvar
-    pub instructions_sysvar: AccountInfo<'info>,
-    #[account(mut)]
+#[instruction(game_id: u32)]
+pub struct FreezeGame<'info> {
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin @ ErrorCode::InvalidAuthority)]
+    pub config: Account<'info, Config>,
+    #[account(address = game_account.authority)]
+    /// CHECK: Game authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game_id.to_le_bytes().as_ref()],
+        bump = game_account.bump,
+        has_one = authority
+    )]
     pub game_account: Account<'info, GameAccount>,
 }
 
-#[init_computation_definition_accounts("vote", payer)]
-#[derive(Accounts)]
-pub struct InitVoteCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: Checked by Arcium program
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+// ===== DATA STRUCTURES =====
+
+/// Program-wide settings, seeds = [b"config"]
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub bump: u8,
+    pub admin: Pubkey,
+    pub paused: bool, // Blocks create_game and submit_vote
+    pub max_players: u8,
+    pub voting_period: i64, // Default voting window for new games in seconds, 0 = no deadline
+    pub creation_fee: u64, // Lamports charged per game
+    pub protocol_fee_bps: u16, // Protocol cut of wager pots
+    pub quorum_bps: u16, // Share of seats that must vote for a reveal after the voting deadline
+}
+
+/// Settable config fields, for initialize_config and update_config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub paused: bool,
+    pub max_players: u8,
+    pub voting_period: i64,
+    pub creation_fee: u64,
+    pub protocol_fee_bps: u16,
+    pub quorum_bps: u16,
+}
+
+impl ConfigParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_players >= 2 && self.max_players <= MAX_PLAYERS,
+            ErrorCode::InvalidPlayerCount
+        );
+        require!(self.voting_period >= 0, ErrorCode::InvalidConfig);
+        require!(
+            self.protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            ErrorCode::InvalidConfig
+        );
+        // At least one ballot is needed to reveal
+        require!(
+            self.quorum_bps > 0 && self.quorum_bps <= MAX_QUORUM_BPS,
+            ErrorCode::InvalidConfig
+        );
+        Ok(())
+    }
+}
+
+impl Config {
+    pub fn apply(&mut self, params: &ConfigParams) {
+        self.paused = params.paused;
+        self.max_players = params.max_players;
+        self.voting_period = params.voting_period;
+        self.creation_fee = params.creation_fee;
+        self.protocol_fee_bps = params.protocol_fee_bps;
+        self.quorum_bps = params.quorum_bps;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GameAccount {
+    pub bump: u8,
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub num_players: u8,
+    pub votes_received: u8,
+    pub status: u8, // GameStatus enum
+    pub nonce: u128,
+    pub voted: u8, // Bitmask by seat of submitted ballots, see VoteAccount
+    pub players: [Pubkey; 8], // Seat owners, default = empty seat
+    pub failed_computation: u8, // ComputationKind awaiting retry, NO_FAILED_COMPUTATION if none
+    pub failed_at: i64,
+    pub retry_count: u8,
+    pub pending_computation: u8, // ComputationKind queued and awaiting its callback, NO_PENDING_COMPUTATION if none
+    pub pending_offset: u64, // computation_offset of the pending computation
+    pub computation_count: u32, // Computations queued so far, seeds the next offset
+    #[max_len(11)]
+    pub room_code: String, // Empty if no room registered
+    pub frozen: bool, // Set by the config admin, halts the game
+    pub wager: u64, // Lamports each player stakes on join, 0 = no wager
+    pub pot: u64, // Escrowed wagers not yet settled
+    pub winners: u8, // Bitmask by seat, set at reveal
+    pub tournament: Pubkey, // Owning tournament, default if none
+    pub rules: u8, // RULE_* bitmask
+    pub sealed_results: [[u8; 32]; 8], // Per seat, "did I win" encrypted to the seat's ballot key
+    pub sealed_roles: [[u8; 32]; 8], // Per seat, "am I the saboteur", secret-saboteur games only
+    pub finished_at: i64, // Set at reveal
+    pub disclosure_delay: i64, // Seconds after finished_at before the host may disclose the saboteur
+    pub mxe_saboteur: [u8; 32], // Saboteur index encrypted to the MXE, host-disclosure games only
+    pub mxe_saboteur_nonce: u128,
+    pub disclosed: bool,
+    pub disclosure_nonce: u128, // Chosen by the host, opens host_sealed_saboteur
+    pub host_sealed_saboteur: [u8; 32], // Saboteur index encrypted to the host's key
+    pub seed_commitment: [u8; 32], // seed_commitment(seed), published when voting opens
+    pub mxe_seed: [[u8; 32]; 2], // Selection seed encrypted to the MXE, as two u128 halves
+    pub mxe_seed_nonce: u128,
+    pub seed: [u8; 32], // Revealed selection seed, zero until reveal or if the saboteur is secret
+    pub saboteur_index: u8, // select_saboteur(seed, num_players), NO_SABOTEUR until revealed or if secret
+    pub voting_period: i64, // Config::voting_period at creation, 0 = no deadline
+    pub voting_deadline: i64, // Set when voting opens, 0 = no deadline
+    pub quorum_bps: u16, // Config::quorum_bps at creation
+    pub confirmed: u8, // Bitmask by seat of tournament seats accepted by their player
+    pub matchmade: bool, // Formed by form_match, anyone may reveal it
+    pub votes_completed_at: i64, // When the last seat voted, 0 until then
+    pub pending_at: i64, // When the pending computation was queued, see COMPUTATION_TIMEOUT_SECONDS
+}
+
+/// Per-authority game counter, seeds = [b"host", authority]
+/// Game ids are assigned from next_game_id, so a host's games are ids 0..next_game_id
+#[account]
+#[derive(InitSpace)]
+pub struct HostState {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub next_game_id: u32,
+}
+
+impl HostState {
+    /// Returns the id for the game being created and advances the counter
+    pub fn allocate_game_id(&mut self, bump: u8, authority: Pubkey) -> u32 {
+        self.bump = bump;
+        self.authority = authority;
+        let game_id = self.next_game_id;
+        self.next_game_id += 1;
+        game_id
+    }
+}
+
+/// One player's ballot, seeds = [b"vote", game, [seat]]
+/// Holds the ciphertext exactly as the player encrypted it to the MXE
+#[account]
+#[derive(InitSpace)]
+pub struct VoteAccount {
+    pub bump: u8,
+    pub game: Pubkey,
+    pub seat: u8,
+    pub player: Pubkey,
+    // Read by the reveal circuit, see BALLOT_OFFSET
+    pub encryption_pubkey: [u8; 32], // Player's ephemeral x25519 key
+    pub nonce: u128,
+    pub encrypted_vote: [u8; 32], // u8: 1 = SAFE, 0 = UNSAFE, 255 = ABSTAIN
+    pub submitted_at: i64,
+}
+
+/// Protocol fee vault, seeds = [b"treasury"]
+/// Fees are held as lamports on this account
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    pub bump: u8,
+    pub total_collected: u64,
+    pub total_withdrawn: u64,
+}
+
+/// Lifetime stats of a wallet, seeds = [b"profile", player]
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub bump: u8,
+    pub player: Pubkey,
+    pub games_played: u32,
+    pub wins: u32,
+    pub saboteur_games: u32,
+    pub saboteur_wins: u32,
+    pub times_caught: u32, // Saboteur games lost
+    pub current_streak: u32, // Consecutive wins
+    pub best_streak: u32,
+    pub rating: u32, // Elo
+}
+
+impl PlayerProfile {
+    /// seeds = [b"profile", player]
+    pub fn address(player: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[b"profile", player.as_ref()], &crate::ID).0
+    }
+
+    pub fn record_game(&mut self, was_saboteur: bool, won: bool) {
+        self.games_played += 1;
+        if won {
+            self.wins += 1;
+            self.current_streak += 1;
+            self.best_streak = self.best_streak.max(self.current_streak);
+        } else {
+            self.current_streak = 0;
+        }
+
+        if was_saboteur {
+            self.saboteur_games += 1;
+            if won {
+                self.saboteur_wins += 1;
+            } else {
+                self.times_caught += 1;
+            }
+        }
+    }
+
+    pub fn apply_rating_delta(&mut self, delta: i64) {
+        self.rating = (self.rating as i64 + delta).max(MIN_RATING as i64) as u32;
+    }
+}
+
+/// Expected score in per-mille for a player rated `diff` points above the opponent
+fn expected_score_permille(diff: i64) -> i64 {
+    let max_diff = ELO_TABLE_STEP * (ELO_EXPECTED_PERMILLE.len() as i64 - 1);
+    let d = diff.abs().min(max_diff);
+    let i = (d / ELO_TABLE_STEP) as usize;
+    let expected = match ELO_EXPECTED_PERMILLE.get(i + 1) {
+        Some(next) => {
+            let lo = ELO_EXPECTED_PERMILLE[i];
+            lo + (next - lo) * (d % ELO_TABLE_STEP) / ELO_TABLE_STEP
+        }
+        None => ELO_EXPECTED_PERMILLE[i],
+    };
+    if diff < 0 {
+        1000 - expected
+    } else {
+        expected
+    }
+}
+
+/// Elo change for every member of a team rated `team` that played a team rated `opponent`
+fn rating_delta(team: i64, opponent: i64, won: bool) -> i64 {
+    let score = if won { 1000 } else { 0 };
+    let change = RATING_K_FACTOR * (score - expected_score_permille(team - opponent));
+    // Round half away from zero
+    (change + change.signum() * 500) / 1000
+}
+
+/// Updates stats and Elo ratings of the seat owners' profiles passed to reveal_result_callback,
+/// one slot per seat; seat owners without a profile are skipped so the result is never lost.
+/// Ratings only move when every seat has a profile: a missing rating cannot be guessed
+fn record_game_in_profiles(
+    profiles: &[AccountInfo],
+    game: &GameAccount,
+    saboteur_index: u8,
+    player_results: &[bool],
+    community_won: bool,
+) -> Result<()> {
+    let num_players = game.num_players as usize;
+    let saboteur = saboteur_index as usize;
+
+    let mut loaded = Vec::new();
+    let mut ratings = [0i64; 8];
+    for (seat, info) in profiles.iter().enumerate().take(num_players) {
+        if info.owner != &crate::ID || info.key() != PlayerProfile::address(&game.players[seat]) {
+            continue;
+        }
+        let Ok(profile) = PlayerProfile::try_deserialize(&mut &info.try_borrow_data()?[..]) else {
+            continue;
+        };
+        ratings[seat] = profile.rating as i64;
+        loaded.push((info, seat, profile));
+    }
+
+    let rated = loaded.len() == num_players;
+
+    // Team ratings: the saboteur alone vs the average of the crew
+    let saboteur_rating = ratings[saboteur.min(num_players - 1)];
+    let crew_rating = (0..num_players)
+        .filter(|&seat| seat != saboteur)
+        .map(|seat| ratings[seat])
+        .sum::<i64>()
+        / (num_players as i64 - 1).max(1);
+    let crew_delta = rating_delta(crew_rating, saboteur_rating, community_won);
+    let slot = Clock::get()?.slot;
+
+    for (info, seat, mut profile) in loaded {
+        let was_saboteur = seat == saboteur;
+        let won = player_results.get(seat).copied().unwrap_or(false);
+        profile.record_game(was_saboteur, won);
+
+        let old_rating = profile.rating;
+        if rated {
+            profile.apply_rating_delta(if was_saboteur { -crew_delta } else { crew_delta });
+        }
+
+        let mut data = info.try_borrow_mut_data()?;
+        profile.try_serialize(&mut &mut data[..])?;
+
+        if rated {
+            emit!(RatingUpdatedEvent {
+                game_id: game.game_id,
+                player: profile.player,
+                seat: seat as u8,
+                was_saboteur,
+                old_rating,
+                new_rating: profile.rating,
+                slot,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Updates the season entries passed to reveal_result_callback: per season a Season account
+/// followed by one entry slot per seat. Seat owners who did not join are skipped, and so are
+/// seasons that are not running
+fn record_game_in_seasons(
+    seasons: &[AccountInfo],
+    game: &GameAccount,
+    player_results: &[bool],
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let num_players = game.num_players as usize;
+
+    for group in seasons.chunks_exact(num_players + 1) {
+        let (season_info, entries) = group.split_first().unwrap();
+        if season_info.owner != &crate::ID {
+            continue;
+        }
+        let Ok(season) = Season::try_deserialize(&mut &season_info.try_borrow_data()?[..]) else {
+            continue;
+        };
+        if now < season.start_ts || now >= season.end_ts {
+            continue;
+        }
+
+        for (seat, info) in entries.iter().enumerate() {
+            let address = SeasonEntry::address(season.season_id, &game.players[seat]);
+            if info.owner != &crate::ID || info.key() != address {
+                continue;
+            }
+            let Ok(mut entry) = SeasonEntry::try_deserialize(&mut &info.try_borrow_data()?[..])
+            else {
+                continue;
+            };
+
+            entry.record_game(player_results.get(seat).copied().unwrap_or(false));
+
+            let mut data = info.try_borrow_mut_data()?;
+            entry.try_serialize(&mut &mut data[..])?;
+        }
+    }
+    Ok(())
+}
+
+/// Competition window, seeds = [b"season", season_id]
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub bump: u8,
+    pub season_id: u32,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub entry_count: u32,
+    pub ranked_entries: u32, // Entries folded into the SeasonResult by close_season
+    pub closed: bool,
+}
+
+/// A player's score in one season, seeds = [b"season_entry", season_id, player]
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonEntry {
+    pub bump: u8,
+    pub season_id: u32,
+    pub player: Pubkey,
+    pub start_ts: i64, // Copied from the season
+    pub end_ts: i64,
+    pub score: u32,
+    pub games_played: u32,
+    pub wins: u32,
+    pub ranked: bool,
+}
+
+impl SeasonEntry {
+    pub fn address(season_id: u32, player: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"season_entry", season_id.to_le_bytes().as_ref(), player.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    pub fn record_game(&mut self, won: bool) {
+        self.games_played += 1;
+        self.score += SEASON_PLAY_POINTS;
+        if won {
+            self.wins += 1;
+            self.score += SEASON_WIN_POINTS;
+        }
+    }
+}
+
+/// Top SEASON_TOP_N entries of a season, seeds = [b"season_result", season_id]
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonResult {
+    pub bump: u8,
+    pub season_id: u32,
+    #[max_len(10)]
+    pub standings: Vec<SeasonStanding>, // Best first
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SeasonStanding {
+    pub player: Pubkey,
+    pub score: u32,
+    pub wins: u32,
+    pub games_played: u32,
+}
+
+impl SeasonResult {
+    /// Ranks by score, then wins; earlier ranked entries keep ties
+    pub fn insert(&mut self, standing: SeasonStanding) {
+        let key = (standing.score, standing.wins);
+        let position = self
+            .standings
+            .iter()
+            .position(|s| (s.score, s.wins) < key)
+            .unwrap_or(self.standings.len());
+        if position < SEASON_TOP_N {
+            self.standings.insert(position, standing);
+            self.standings.truncate(SEASON_TOP_N);
+        }
+    }
 }
 
-#[queue_computation_accounts("reveal_result", payer)]
-#[derive(Accounts)]
-#[instruction(computation_offset: u64, game_id: u32)]
-pub struct RevealResult<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(
-        init_if_needed,
-        space = 9,
-        payer = payer,
-        seeds = [&SIGN_PDA_SEED],
-        bump,
-        address = derive_sign_pda!(),
-    )]
-    pub sign_pda_account: Account<'info, ArciumSignerAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    #[account(mut, address = derive_mempool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub mempool_account: UncheckedAccount<'info>,
-    #[account(mut, address = derive_execpool_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub executing_pool: UncheckedAccount<'info>,
-    #[account(mut, address = derive_comp_pda!(computation_offset, mxe_account, ErrorCode::ClusterNotSet))]
-    /// CHECK: Checked by Arcium program
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(mut, address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(mut, address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS)]
-    pub pool_account: Account<'info, FeePool>,
-    #[account(mut, address = ARCIUM_CLOCK_ACCOUNT_ADDRESS)]
-    pub clock_account: Account<'info, ClockAccount>,
-    pub system_program: Program<'info, System>,
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(
-        mut,
-        seeds = [b"game", payer.key().as_ref(), game_id.to_le_bytes().as_ref()],
-        bump = game_account.bump
-    )]
-    pub game_account: Account<'info, GameAccount>,
+/// Public matchmaking queue, seeds = [b"match_queue", [num_players], stake]
+/// Queued stakes are held on this account until form_match or leave_queue
+#[account]
+#[derive(InitSpace)]
+pub struct MatchQueue {
+    pub bump: u8,
+    pub num_players: u8,
+    pub stake: u64, // Lamports per player
+    #[max_len(32)]
+    pub entries: Vec<QueueEntry>, // Oldest first
 }
 
-#[callback_accounts("reveal_result")]
-#[derive(Accounts)]
-pub struct RevealResultCallback<'info> {
-    pub arcium_program: Program<'info, Arcium>,
-    #[account(address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL))]
-    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
-    #[account(address = derive_mxe_pda!())]
-    pub mxe_account: Account<'info, MXEAccount>,
-    /// CHECK: Checked by Arcium program
-    pub computation_account: UncheckedAccount<'info>,
-    #[account(address = derive_cluster_pda!(mxe_account, ErrorCode::ClusterNotSet))]
-    pub cluster_account: Account<'info, Cluster>,
-    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
-    /// CHECK: Sysvar
-    pub instructions_sysvar: AccountInfo<'info>,
-    #[account(mut)]
-    pub game_account: Account<'info, GameAccount>,
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct QueueEntry {
+    pub player: Pubkey,
+    pub rating: u32, // From the profile at enqueue time, DEFAULT_RATING without one
+    pub rating_band: u16, // Max rating gap to the other players, 0 = any
+    pub enqueued_at: i64,
 }
 
-#[init_computation_definition_accounts("reveal_result", payer)]
-#[derive(Accounts)]
-pub struct InitRevealCompDef<'info> {
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    #[account(mut, address = derive_mxe_pda!())]
-    pub mxe_account: Box<Account<'info, MXEAccount>>,
-    #[account(mut)]
-    /// CHECK: Checked by Arcium program
-    pub comp_def_account: UncheckedAccount<'info>,
-    pub arcium_program: Program<'info, Arcium>,
-    pub system_program: Program<'info, System>,
+impl QueueEntry {
+    fn accepts(&self, other: &QueueEntry) -> bool {
+        self.rating_band == 0 || self.rating.abs_diff(other.rating) <= self.rating_band as u32
+    }
 }
 
-// ===== DATA STRUCTURES =====
+impl MatchQueue {
+    /// Entry indices (ascending) of the first group of num_players mutually compatible players,
+    /// preferring those waiting longest
+    pub fn find_match(&self) -> Option<Vec<usize>> {
+        let needed = self.num_players as usize;
+        for anchor in 0..self.entries.len() {
+            let mut group = vec![anchor];
+            for candidate in anchor + 1..self.entries.len() {
+                let entry = &self.entries[candidate];
+                if group
+                    .iter()
+                    .all(|&i| self.entries[i].accepts(entry) && entry.accepts(&self.entries[i]))
+                {
+                    group.push(candidate);
+                    if group.len() == needed {
+                        return Some(group);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
 
+/// Multi-round bracket, seeds = [b"tournament", organizer, tournament_id]
+/// Prize pool lamports are held on this account
 #[account]
 #[derive(InitSpace)]
-pub struct GameAccount {
+pub struct Tournament {
     pub bump: u8,
-    pub game_id: u32,
+    pub tournament_id: u32,
+    pub organizer: Pubkey,
+    pub status: u8, // TournamentStatus enum
+    pub entry_fee: u64,
+    pub prize_pool: u64,
+    pub max_entrants: u8,
+    pub round: u8, // 0 until started
+    #[max_len(32)]
+    pub roster: Vec<Pubkey>, // Players still in, champions once finished
+    pub seated: u8, // Roster players seated into this round's games
+    #[max_len(16)]
+    pub games: Vec<Pubkey>, // This round's games, in seating order
+    pub round_started_at: i64,
+}
+
+/// Room code -> game lookup, seeds = [b"room", room_code]
+#[account]
+#[derive(InitSpace)]
+pub struct RoomIndex {
+    pub bump: u8,
+    #[max_len(11)]
+    pub room_code: String,
+    pub game: Pubkey,
     pub authority: Pubkey,
-    pub num_players: u8,
-    pub votes_received: u8,
-    pub status: u8, // GameStatus enum
-    pub nonce: u128,
-    pub vote_state: [[u8; 32]; 8], // Max 8 players, encrypted votes
+    pub game_id: u32,
+}
+
+/// game_logic::check_rules with its errors mapped to ErrorCode
+fn check_rules(rules: u8, wager: u64, disclosure_delay: i64) -> Result<()> {
+    game_logic::check_rules(rules, wager, disclosure_delay).map_err(|e| match e {
+        RulesError::Invalid => error!(ErrorCode::InvalidRules),
+        RulesError::PrivateResultsUnsupported => error!(ErrorCode::PrivateResultsUnsupported),
+    })
+}
+
+fn is_valid_room_code(room_code: &str) -> bool {
+    match room_code.strip_prefix(ROOM_CODE_PREFIX) {
+        Some(suffix) => {
+            suffix.len() == ROOM_CODE_SUFFIX_LEN
+                && suffix.bytes().all(|c| ROOM_CODE_ALPHABET.contains(&c))
+        }
+        None => false,
+    }
+}
+
+impl GameAccount {
+    /// Allocates the next computation offset of this game and records it as pending;
+    /// its callback is the only one accepted
+    pub fn begin_computation(&mut self, game: Pubkey, kind: ComputationKind) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        self.expire_computation(now);
+
+        let computation_offset = derive_computation_offset(&game, self.computation_count);
+        require!(
+            game_logic::begin_computation(
+                &mut self.pending_computation,
+                &mut self.pending_offset,
+                kind as u8,
+                computation_offset,
+            ),
+            ErrorCode::ComputationPending
+        );
+        self.computation_count += 1;
+        self.pending_at = now;
+        Ok(computation_offset)
+    }
+
+    pub fn end_computation(&mut self) {
+        self.pending_computation = NO_PENDING_COMPUTATION;
+    }
+
+    pub fn computation_timed_out(&self, now: i64) -> bool {
+        game_logic::computation_timed_out(self.pending_computation, self.pending_at, now)
+    }
+
+    /// Gives up on a pending computation that never called back: it is recorded as failed,
+    /// as if it had aborted when it timed out, and its late callback is no longer accepted.
+    /// A disclosure has nothing to retry, the host simply discloses again
+    pub fn expire_computation(&mut self, now: i64) {
+        if !self.computation_timed_out(now) {
+            return;
+        }
+
+        if self.pending_computation != ComputationKind::Disclose as u8 {
+            self.failed_computation = self.pending_computation;
+            self.failed_at = self.pending_at + COMPUTATION_TIMEOUT_SECONDS;
+        }
+        self.end_computation();
+    }
+
+    pub fn record_failure(&mut self, kind: ComputationKind, now: i64) {
+        self.failed_computation = kind as u8;
+        self.failed_at = now;
+    }
+
+    /// Circuit arguments and callback accounts of reveal_result and retry_reveal_result
+    /// remaining_accounts: every voted seat's VoteAccount in seat order, then the stats
+    /// accounts, see reveal_callback_accounts
+    pub fn reveal_arguments(
+        &self,
+        game: Pubkey,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<(ArgumentList, Vec<CallbackAccount>)> {
+        let ballots = self.voted.count_ones() as usize;
+        require!(
+            remaining_accounts.len() >= ballots,
+            ErrorCode::VoteAccountsMismatch
+        );
+        let (vote_accounts, stats_accounts) = remaining_accounts.split_at(ballots);
+        GameAccount::check_vote_accounts(game, self.voted, vote_accounts)?;
+
+        let mut args = ArgBuilder::new()
+            .plaintext_u8(self.num_players)
+            .plaintext_u8(self.voted)
+            .plaintext_bool(self.private_results())
+            .plaintext_bool(self.secret_saboteur())
+            .plaintext_u128(self.mxe_seed_nonce)
+            .encrypted_u128(self.mxe_seed[0])
+            .encrypted_u128(self.mxe_seed[1]);
+        for vote in vote_accounts {
+            args = args.account(vote.key(), BALLOT_OFFSET, BALLOT_LEN);
+        }
+
+        let callback_accounts = self.reveal_callback_accounts(game, stats_accounts)?;
+        Ok((args.build(), callback_accounts))
+    }
+
+    /// Callback accounts for reveal_result: the game, then the PlayerProfile PDA of every seat
+    /// in seat order, then for each season to score its Season account and the SeasonEntry PDA
+    /// of every seat. Profiles and entries are passed whether or not they exist, so no seat
+    /// owner's stats can be left out
+    pub fn reveal_callback_accounts(
+        &self,
+        game: Pubkey,
+        stats_accounts: &[AccountInfo],
+    ) -> Result<Vec<CallbackAccount>> {
+        let mut accounts = vec![CallbackAccount {
+            pubkey: game,
+            is_writable: true,
+        }];
+
+        let num_players = self.num_players as usize;
+        let players = &self.players[..num_players];
+        require!(
+            stats_accounts.len() >= num_players
+                && (stats_accounts.len() - num_players) % (num_players + 1) == 0,
+            ErrorCode::InvalidStatsAccount
+        );
+        let (profiles, seasons) = stats_accounts.split_at(num_players);
+        for (player, info) in players.iter().zip(profiles) {
+            require!(
+                info.key() == PlayerProfile::address(player),
+                ErrorCode::InvalidStatsAccount
+            );
+            accounts.push(CallbackAccount {
+                pubkey: info.key(),
+                is_writable: true,
+            });
+        }
+
+        for group in seasons.chunks(num_players + 1) {
+            let (season_info, entries) = group.split_first().unwrap();
+            require!(season_info.owner == &crate::ID, ErrorCode::InvalidStatsAccount);
+            require!(
+                accounts.iter().all(|a| a.pubkey != season_info.key()),
+                ErrorCode::InvalidStatsAccount
+            );
+            let Ok(season) = Season::try_deserialize(&mut &season_info.try_borrow_data()?[..])
+            else {
+                return err!(ErrorCode::InvalidStatsAccount);
+            };
+            accounts.push(CallbackAccount {
+                pubkey: season_info.key(),
+                is_writable: false,
+            });
+
+            for (player, info) in players.iter().zip(entries) {
+                require!(
+                    info.key() == SeasonEntry::address(season.season_id, player),
+                    ErrorCode::InvalidStatsAccount
+                );
+                accounts.push(CallbackAccount {
+                    pubkey: info.key(),
+                    is_writable: true,
+                });
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    pub fn private_results(&self) -> bool {
+        self.rules & RULE_PRIVATE_RESULTS != 0
+    }
+
+    pub fn secret_saboteur(&self) -> bool {
+        self.rules & RULE_SECRET_SABOTEUR != 0
+    }
+
+    /// Whether a revealed seed opens this game's commitment and selects saboteur_index
+    pub fn seed_matches(&self, seed: &[u8; 32], saboteur_index: u8) -> bool {
+        game_logic::seed_matches(&self.seed_commitment, self.num_players, seed, saboteur_index)
+    }
+
+    pub fn has_voted(&self, seat: u8) -> bool {
+        game_logic::has_voted(self.voted, seat)
+    }
+
+    /// Counts a seat's ballot; returns false if the seat was already counted
+    pub fn record_vote(&mut self, seat: u8) -> bool {
+        let counted = game_logic::record_vote(&mut self.voted, self.num_players, seat);
+        self.votes_received = self.voted.count_ones() as u8;
+        counted
+    }
+
+    /// Vote accounts must be the game's VoteAccount PDAs of the voted seats, in seat order
+    pub fn check_vote_accounts(game: Pubkey, voted: u8, vote_accounts: &[AccountInfo]) -> Result<()> {
+        let seats = (0..8u8).filter(|seat| voted & (1 << seat) != 0);
+        for (seat, info) in seats.zip(vote_accounts) {
+            let (expected, _) =
+                Pubkey::find_program_address(&[b"vote", game.as_ref(), &[seat]], &crate::ID);
+            require!(
+                info.key() == expected && info.owner == &crate::ID,
+                ErrorCode::VoteAccountsMismatch
+            );
+        }
+        Ok(())
+    }
+
+    /// Whether the voting deadline passed; games without a deadline never close
+    pub fn voting_closed(&self, now: i64) -> bool {
+        game_logic::voting_closed(self.voting_deadline, now)
+    }
+
+    /// Whether caller may queue the reveal: the authority always, anyone for matchmade games,
+    /// and anyone REVEAL_TIMEOUT_SECONDS after every seat voted or the voting deadline passed
+    pub fn may_reveal(&self, caller: Pubkey, now: i64) -> bool {
+        if caller == self.authority || self.matchmade {
+            return true;
+        }
+        let ready_at = if self.votes_received == self.num_players {
+            self.votes_completed_at
+        } else {
+            self.voting_deadline
+        };
+        ready_at != 0 && now >= ready_at.saturating_add(REVEAL_TIMEOUT_SECONDS)
+    }
+
+    pub fn quorum_reached(&self) -> bool {
+        game_logic::quorum_reached(self.votes_received, self.num_players, self.quorum_bps)
+    }
+
+    /// A game that can no longer finish: its computation failed or timed out for good, or
+    /// voting closed without a quorum. Such games are refunded and can be closed
+    pub fn abandoned(&self, now: i64) -> bool {
+        self.retries_exhausted()
+            || (self.computation_timed_out(now)
+                && self.pending_computation != ComputationKind::Disclose as u8
+                && self.retry_count >= MAX_COMPUTATION_RETRIES)
+            || (self.status == GameStatus::Voting as u8
+                && self.voting_closed(now)
+                && !self.quorum_reached())
+    }
+
+    pub fn retries_exhausted(&self) -> bool {
+        self.failed_computation != NO_FAILED_COMPUTATION
+            && self.retry_count >= MAX_COMPUTATION_RETRIES
+    }
+
+    /// Retry budget is per computation, so a success resets it
+    pub fn clear_failure(&mut self) {
+        self.failed_computation = NO_FAILED_COMPUTATION;
+        self.retry_count = 0;
+    }
+
+    /// Checks that a failed or timed-out computation of `kind` may be retried by `caller`
+    /// and uses up one retry
+    pub fn begin_retry(&mut self, kind: ComputationKind, caller: Pubkey, now: i64) -> Result<()> {
+        require!(!self.frozen, ErrorCode::GameFrozen);
+        self.expire_computation(now);
+        require!(
+            self.failed_computation == kind as u8,
+            ErrorCode::NoFailedComputation
+        );
+        require!(
+            self.retry_count < MAX_COMPUTATION_RETRIES,
+            ErrorCode::RetryLimitReached
+        );
+        require!(
+            caller == self.authority || now >= self.failed_at + RETRY_COOLDOWN_SECONDS,
+            ErrorCode::RetryCooldown
+        );
+
+        self.failed_computation = NO_FAILED_COMPUTATION;
+        self.retry_count += 1;
+        Ok(())
+    }
 }
 
 #[repr(u8)]
@@ -532,6 +3376,28 @@ pub enum GameStatus {
     Finished = 3,
 }
 
+#[repr(u8)]
+pub enum ComputationKind {
+    InitGame = 0,
+    // 1 was the vote circuit, votes are now stored directly in VoteAccounts
+    Reveal = 2,
+    Disclose = 3,
+}
+
+#[repr(u8)]
+pub enum FeeKind {
+    Creation = 0,
+    Pot = 1, // Protocol cut of a wager pot, plus rounding dust
+    Tournament = 2, // Cut of a prize pool; game_id holds the tournament id
+}
+
+#[repr(u8)]
+pub enum TournamentStatus {
+    Registration = 0,
+    Running = 1,
+    Finished = 2,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Invalid player count (must be 2-8)")]
@@ -546,11 +3412,108 @@ pub enum ErrorCode {
     InvalidGameStatus,
     #[msg("Invalid player index")]
     InvalidPlayerIndex,
+    #[msg("Seat already taken")]
+    SeatTaken,
+    #[msg("Player already joined this game")]
+    AlreadyJoined,
+    #[msg("Signer does not own this seat")]
+    NotSeatOwner,
+    #[msg("No failed computation of this kind to retry")]
+    NoFailedComputation,
+    #[msg("Computation retry limit reached")]
+    RetryLimitReached,
+    #[msg("Only the authority can retry before the cooldown ends")]
+    RetryCooldown,
+    #[msg("Invalid room code (expected VEIL-XXXXXX)")]
+    InvalidRoomCode,
+    #[msg("Room code is already in use")]
+    RoomCodeTaken,
+    #[msg("Game already has a room code")]
+    RoomAlreadyRegistered,
+    #[msg("Room index must be closed together with the game")]
+    RoomIndexRequired,
+    #[msg("Room index does not point to this game")]
+    RoomIndexMismatch,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Invalid config value")]
+    InvalidConfig,
+    #[msg("Game is frozen")]
+    GameFrozen,
+    #[msg("Game is not frozen")]
+    GameNotFrozen,
+    #[msg("Wager pot must be settled before closing")]
+    PotNotSettled,
+    #[msg("No wager pot to settle")]
+    NothingToSettle,
+    #[msg("Remaining accounts must be the seat owners in seat order")]
+    SeatAccountsMismatch,
+    #[msg("Treasury balance too low")]
+    InsufficientTreasury,
+    #[msg("Stats accounts must be every seat's profile PDA, then per season every seat's entry")]
+    InvalidStatsAccount,
+    #[msg("Season must end after it starts")]
+    InvalidSeason,
+    #[msg("Season has ended")]
+    SeasonEnded,
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+    #[msg("Season is already closed")]
+    SeasonClosed,
+    #[msg("Not an unranked entry of this season")]
+    InvalidSeasonEntry,
+    #[msg("Invalid tournament settings")]
+    InvalidTournament,
+    #[msg("Invalid tournament status")]
+    InvalidTournamentStatus,
+    #[msg("Tournament is full")]
+    TournamentFull,
+    #[msg("Game belongs to a tournament or already has players")]
+    TournamentGame,
+    #[msg("Remaining accounts must be the round's games in order")]
+    TournamentGameMismatch,
+    #[msg("Match queue is full")]
+    QueueFull,
+    #[msg("Player is not in this queue")]
+    NotQueued,
+    #[msg("Not enough compatible players queued")]
+    NoMatch,
+    #[msg("This seat has already voted")]
+    AlreadyVoted,
+    #[msg("Remaining accounts must start with every seat's vote account in order")]
+    VoteAccountsMismatch,
+    #[msg("A computation is already pending for this game")]
+    ComputationPending,
+    #[msg("Callback does not match the computation the game is waiting for")]
+    UnexpectedComputation,
+    #[msg("Unknown game rules")]
+    InvalidRules,
+    #[msg("Games with private results cannot be wagered or played in tournaments")]
+    PrivateResultsUnsupported,
+    #[msg("This game's saboteur cannot be disclosed")]
+    DisclosureNotAllowed,
+    #[msg("The disclosure delay has not passed yet")]
+    DisclosureTooEarly,
+    #[msg("The saboteur has already been disclosed")]
+    AlreadyDisclosed,
+    #[msg("The voting deadline has passed")]
+    VotingClosed,
+    #[msg("Not every seat has voted and the voting deadline has not passed")]
+    VotingStillOpen,
+    #[msg("Too few seats voted before the deadline")]
+    QuorumNotReached,
+    #[msg("The player has not confirmed this tournament seat")]
+    SeatNotConfirmed,
+    #[msg("This tournament seat is already confirmed")]
+    SeatAlreadyConfirmed,
+    #[msg("Stake times player count overflows")]
+    StakeOverflow,
 }
 
 #[event]
 pub struct VoteSubmittedEvent {
     pub game_id: u32,
+    pub authority: Pubkey,
     pub votes_received: u8,
     pub total_players: u8,
 }
@@ -558,9 +3521,154 @@ pub struct VoteSubmittedEvent {
 #[event]
 pub struct GameResultEvent {
     pub game_id: u32,
-    pub saboteur_index: u8,
-    pub saboteur_voted_safe: bool,
-    pub community_voted_safe: bool,
+    pub authority: Pubkey,
+    pub saboteur_index: u8, // NO_SABOTEUR if secret
+    pub saboteur_voted_safe: bool, // False if private
+    pub community_voted_safe: bool, // False if private
     pub community_won: bool,
-    pub player_results: Vec<bool>, // Per-player: did they win?
+    pub player_results: Vec<bool>, // Per-player: did they win? Empty if private
+    pub rules: u8, // RULE_* bitmask
+    pub sealed_results: Vec<[u8; 32]>, // Per-player result encrypted to their ballot key, if private
+    pub sealed_roles: Vec<[u8; 32]>, // Per-player "am I the saboteur", if the saboteur is secret
+    pub seed: [u8; 32], // Selection seed, zero if the saboteur is secret
+}
+
+#[event]
+pub struct GameCreatedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub num_players: u8,
+    pub slot: u64,
+}
+
+#[event]
+pub struct VotingOpenedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub seed_commitment: [u8; 32], // Fixes the saboteur, opened by GameResultEvent::seed
+    pub slot: u64,
+}
+
+#[event]
+pub struct ComputationQueuedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub computation_offset: u64,
+    pub kind: u8, // ComputationKind enum
+    pub slot: u64,
+}
+
+#[event]
+pub struct ComputationAbortedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub kind: u8, // ComputationKind enum
+    pub retry_count: u8, // Retries used so far
+    pub slot: u64,
+}
+
+#[event]
+pub struct GameClosedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GameFrozenEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GameUnfrozenEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
+}
+
+#[event]
+pub struct FeeCollectedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub kind: u8, // FeeKind enum
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct GameSettledEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub pot: u64,
+    pub protocol_fee: u64, // Sent to the treasury, including rounding dust
+    pub payout: u64, // Per paid seat
+    pub paid_seats: u8, // Bitmask by seat
+    pub refunded: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawnEvent {
+    pub admin: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct RatingUpdatedEvent {
+    pub game_id: u32,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub was_saboteur: bool,
+    pub old_rating: u32,
+    pub new_rating: u32,
+    pub slot: u64,
+}
+
+#[event]
+pub struct SeasonClosedEvent {
+    pub season_id: u32,
+    pub entry_count: u32,
+    pub winner: Pubkey, // Default if the season had no entries
+    pub slot: u64,
+}
+
+#[event]
+pub struct TournamentAdvancedEvent {
+    pub tournament_id: u32,
+    pub organizer: Pubkey,
+    pub round: u8, // Round now being played, or the last one if finished
+    pub remaining_players: u8,
+    pub finished: bool,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TournamentPrizesDistributedEvent {
+    pub tournament_id: u32,
+    pub organizer: Pubkey,
+    pub prize_pool: u64,
+    pub protocol_fee: u64, // Including rounding dust
+    pub payout: u64, // Per champion
+    pub champions: u8,
+    pub slot: u64,
+}
+
+#[event]
+pub struct MatchFormedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub stake: u64,
+    pub players: Vec<Pubkey>, // By seat
+    pub slot: u64,
+}
+
+#[event]
+pub struct SaboteurDisclosedEvent {
+    pub game_id: u32,
+    pub authority: Pubkey,
+    pub slot: u64,
 }
@@ -1,16 +1,41 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+pub mod game_logic;
 
 declare_id!("51JDkhaM8nWP3NEEtDAs28WKZH8bM5Wr6YGVyuMxHfZu");
 
+const MAX_PLAYERS: u8 = 8; // Size of voters / commitments / votes
+
 #[program]
 pub mod veil {
     use super::*;
 
-    pub fn create_game(ctx: Context<CreateGame>, room_code: String, player_count: u8) -> Result<()> {
+    /// Votes are committed until now + commit_period (seconds), then revealed until
+    /// reveal_period seconds after that
+    pub fn create_game(
+        ctx: Context<CreateGame>,
+        room_code: String,
+        player_count: u8,
+        commit_period: i64,
+        reveal_period: i64,
+    ) -> Result<()> {
+        require!((2..=MAX_PLAYERS).contains(&player_count), ErrorCode::InvalidPlayerCount);
+        require!(commit_period > 0 && reveal_period > 0, ErrorCode::InvalidDeadline);
+
+        let now = Clock::get()?.unix_timestamp;
         let game = &mut ctx.accounts.game;
+        game.authority = ctx.accounts.authority.key();
         game.room_code = room_code;
         game.player_count = player_count;
+        game.voters = vec![Pubkey::default(); player_count as usize];
+        game.commitments = vec![[0; 32]; player_count as usize];
         game.votes = vec![0; player_count as usize];
+        game.commit_deadline = now.checked_add(commit_period).ok_or(ErrorCode::InvalidDeadline)?;
+        game.reveal_deadline = game
+            .commit_deadline
+            .checked_add(reveal_period)
+            .ok_or(ErrorCode::InvalidDeadline)?;
         game.status = GameStatus::WaitingForVotes;
         game.saboteur_index = 255; // Will be set during reveal
         
@@ -18,32 +43,71 @@ pub mod veil {
         Ok(())
     }
 
-    pub fn submit_vote(ctx: Context<SubmitVote>, player_index: u8, vote: u8) -> Result<()> {
+    /// Commit to a vote without revealing it: commitment = vote_commitment(vote, salt, voter)
+    /// The first commit claims the seat for the signer; it may be replaced until the commit deadline
+    pub fn commit_vote(ctx: Context<CommitVote>, player_index: u8, commitment: [u8; 32]) -> Result<()> {
         let game = &mut ctx.accounts.game;
+        let voter = ctx.accounts.voter.key();
         require!(player_index < game.player_count, ErrorCode::InvalidPlayerIndex);
         require!(game.status == GameStatus::WaitingForVotes, ErrorCode::InvalidGameStatus);
+        game.check_commit_open(Clock::get()?.unix_timestamp)?;
+
+        let seat = player_index as usize;
+        require!(
+            game.voters[seat] == Pubkey::default() || game.voters[seat] == voter,
+            ErrorCode::SeatTaken
+        );
+        require!(
+            !game.voters.iter().enumerate().any(|(i, v)| i != seat && *v == voter),
+            ErrorCode::SeatTaken
+        );
+
+        game.voters[seat] = voter;
+        game.commitments[seat] = commitment;
         
+        emit!(VoteCommittedEvent {
+            room_code: game.room_code.clone(),
+            player_index,
+        });
+        
+        Ok(())
+    }
+
+    /// Open a committed vote once the commit phase is over (1 = SAFE, 2 = UNSAFE)
+    pub fn reveal_vote(ctx: Context<RevealVote>, player_index: u8, vote: u8, salt: [u8; 32]) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        let voter = ctx.accounts.voter.key();
+        require!(player_index < game.player_count, ErrorCode::InvalidPlayerIndex);
+        require!(game.status == GameStatus::WaitingForVotes, ErrorCode::InvalidGameStatus);
+
+        game.check_reveal_open(Clock::get()?.unix_timestamp)?;
+        game.check_reveal(player_index, &voter, vote, &salt)?;
+
         game.votes[player_index as usize] = vote;
         
-        emit!(VoteSubmittedEvent {
+        emit!(VoteRevealedEvent {
             room_code: game.room_code.clone(),
             player_index,
+            vote,
         });
         
         Ok(())
     }
 
+    /// Only revealed ballots are counted; committed but unrevealed votes are ignored
+    /// Callable once the reveal deadline passed, or earlier if every committed vote is revealed
     pub fn reveal_result(ctx: Context<RevealResult>) -> Result<()> {
         let game = &mut ctx.accounts.game;
         require!(game.status == GameStatus::WaitingForVotes, ErrorCode::InvalidGameStatus);
+
+        let clock = Clock::get()?;
+        game.check_result_ready(clock.unix_timestamp)?;
         
         // Simple random saboteur selection (use clock for randomness)
-        let clock = Clock::get()?;
         game.saboteur_index = (clock.unix_timestamp % game.player_count as i64) as u8;
         
         // Count votes
-        let safe_votes: u8 = game.votes.iter().filter(|&&v| v == 1).count() as u8;
-        let unsafe_votes: u8 = game.votes.iter().filter(|&&v| v == 2).count() as u8;
+        let (safe_votes, unsafe_votes) = game.tally();
         
         // Determine winner
         let saboteur_vote = game.votes[game.saboteur_index as usize];
@@ -74,7 +138,7 @@ pub struct CreateGame<'info> {
         init,
         payer = authority,
         space = 8 + GameAccount::INIT_SPACE,
-        seeds = [b"game", room_code.as_bytes()],
+        seeds = [b"game", authority.key().as_ref(), room_code.as_bytes()],
         bump
     )]
     pub game: Account<'info, GameAccount>,
@@ -84,37 +148,113 @@ pub struct CreateGame<'info> {
 }
 
 #[derive(Accounts)]
-pub struct SubmitVote<'info> {
+pub struct CommitVote<'info> {
     #[account(
         mut,
-        seeds = [b"game", game.room_code.as_bytes()],
+        seeds = [b"game", game.authority.as_ref(), game.room_code.as_bytes()],
         bump
     )]
     pub game: Account<'info, GameAccount>,
+    pub voter: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct RevealResult<'info> {
+pub struct RevealVote<'info> {
     #[account(
         mut,
-        seeds = [b"game", game.room_code.as_bytes()],
+        seeds = [b"game", game.authority.as_ref(), game.room_code.as_bytes()],
         bump
     )]
     pub game: Account<'info, GameAccount>,
+    pub voter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealResult<'info> {
+    #[account(
+        mut,
+        seeds = [b"game", authority.key().as_ref(), game.room_code.as_bytes()],
+        bump,
+        has_one = authority @ ErrorCode::InvalidAuthority
+    )]
+    pub game: Account<'info, GameAccount>,
+    pub authority: Signer<'info>,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct GameAccount {
+    pub authority: Pubkey, // Host; room codes are namespaced per host
     #[max_len(6)]
     pub room_code: String,
     pub player_count: u8,
     #[max_len(8)]
-    pub votes: Vec<u8>,
+    pub voters: Vec<Pubkey>, // Seat owners, claimed by their first commit
+    #[max_len(8)]
+    pub commitments: Vec<[u8; 32]>,
+    #[max_len(8)]
+    pub votes: Vec<u8>, // Revealed votes: 0 = not revealed, 1 = SAFE, 2 = UNSAFE
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
     pub saboteur_index: u8,
     pub status: GameStatus,
 }
 
+impl GameAccount {
+    pub fn check_commit_open(&self, now: i64) -> Result<()> {
+        require!(now < self.commit_deadline, ErrorCode::CommitPhaseOver);
+        Ok(())
+    }
+
+    /// Votes are revealed from the commit deadline until the reveal deadline
+    pub fn check_reveal_open(&self, now: i64) -> Result<()> {
+        require!(now >= self.commit_deadline, ErrorCode::CommitPhaseActive);
+        require!(now < self.reveal_deadline, ErrorCode::RevealPhaseOver);
+        Ok(())
+    }
+
+    /// Checks that voter owns the seat and that vote and salt open its commitment
+    pub fn check_reveal(&self, player_index: u8, voter: &Pubkey, vote: u8, salt: &[u8; 32]) -> Result<()> {
+        let seat = player_index as usize;
+        require!(self.voters[seat] == *voter, ErrorCode::InvalidAuthority);
+        require!(self.votes[seat] == 0, ErrorCode::AlreadyRevealed);
+        require!(vote == 1 || vote == 2, ErrorCode::InvalidVote);
+        require!(
+            vote_commitment(vote, salt, voter) == self.commitments[seat],
+            ErrorCode::CommitmentMismatch
+        );
+        Ok(())
+    }
+
+    /// The result is ready once the reveal deadline passed, or earlier if every committed
+    /// vote is revealed
+    pub fn check_result_ready(&self, now: i64) -> Result<()> {
+        require!(now >= self.commit_deadline, ErrorCode::CommitPhaseActive);
+        let all_revealed = self
+            .voters
+            .iter()
+            .zip(&self.votes)
+            .all(|(voter, vote)| *voter == Pubkey::default() || *vote != 0);
+        require!(
+            all_revealed || now >= self.reveal_deadline,
+            ErrorCode::RevealPhaseActive
+        );
+        Ok(())
+    }
+
+    /// SAFE and UNSAFE votes among the revealed ballots
+    pub fn tally(&self) -> (u8, u8) {
+        let safe_votes = self.votes.iter().filter(|&&v| v == 1).count() as u8;
+        let unsafe_votes = self.votes.iter().filter(|&&v| v == 2).count() as u8;
+        (safe_votes, unsafe_votes)
+    }
+}
+
+/// sha256(vote || salt || voter); binding the voter stops others from copying a commitment
+pub fn vote_commitment(vote: u8, salt: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+    hashv(&[&[vote], salt.as_ref(), voter.as_ref()]).to_bytes()
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum GameStatus {
     WaitingForVotes,
@@ -124,11 +264,18 @@ pub enum GameStatus {
 }
 
 #[event]
-pub struct VoteSubmittedEvent {
+pub struct VoteCommittedEvent {
     pub room_code: String,
     pub player_index: u8,
 }
 
+#[event]
+pub struct VoteRevealedEvent {
+    pub room_code: String,
+    pub player_index: u8,
+    pub vote: u8,
+}
+
 #[event]
 pub struct GameResultEvent {
     pub room_code: String,
@@ -144,4 +291,130 @@ pub enum ErrorCode {
     InvalidPlayerIndex,
     #[msg("Invalid game status")]
     InvalidGameStatus,
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+    #[msg("Seat is claimed by another voter")]
+    SeatTaken,
+    #[msg("Commit and reveal periods must be positive")]
+    InvalidDeadline,
+    #[msg("Commit deadline has passed")]
+    CommitPhaseOver,
+    #[msg("Votes cannot be revealed before the commit deadline")]
+    CommitPhaseActive,
+    #[msg("Reveal deadline has passed")]
+    RevealPhaseOver,
+    #[msg("Committed votes are still being revealed")]
+    RevealPhaseActive,
+    #[msg("Vote already revealed")]
+    AlreadyRevealed,
+    #[msg("Vote must be 1 (SAFE) or 2 (UNSAFE)")]
+    InvalidVote,
+    #[msg("Vote and salt do not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Invalid player count (must be 2-8)")]
+    InvalidPlayerCount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitment_binds_vote_salt_and_voter() {
+        let voter = Pubkey::new_unique();
+        let salt = [7; 32];
+        let commitment = vote_commitment(1, &salt, &voter);
+
+        assert_eq!(commitment, vote_commitment(1, &salt, &voter));
+        assert_ne!(commitment, vote_commitment(2, &salt, &voter));
+        assert_ne!(commitment, vote_commitment(1, &[8; 32], &voter));
+        assert_ne!(commitment, vote_commitment(1, &salt, &Pubkey::new_unique()));
+    }
+
+    // Commits until 100, reveals until 200
+    fn game(player_count: u8) -> GameAccount {
+        GameAccount {
+            authority: Pubkey::new_unique(),
+            room_code: "ABC123".to_string(),
+            player_count,
+            voters: vec![Pubkey::default(); player_count as usize],
+            commitments: vec![[0; 32]; player_count as usize],
+            votes: vec![0; player_count as usize],
+            commit_deadline: 100,
+            reveal_deadline: 200,
+            saboteur_index: 255,
+            status: GameStatus::WaitingForVotes,
+        }
+    }
+
+    fn commit(game: &mut GameAccount, seat: usize, vote: u8, salt: &[u8; 32]) -> Pubkey {
+        let voter = Pubkey::new_unique();
+        game.voters[seat] = voter;
+        game.commitments[seat] = vote_commitment(vote, salt, &voter);
+        voter
+    }
+
+    #[test]
+    fn commits_close_at_the_commit_deadline() {
+        let game = game(3);
+
+        assert!(game.check_commit_open(99).is_ok());
+        assert_eq!(game.check_commit_open(100), Err(ErrorCode::CommitPhaseOver.into()));
+        assert_eq!(game.check_commit_open(250), Err(ErrorCode::CommitPhaseOver.into()));
+    }
+
+    #[test]
+    fn reveals_only_inside_the_reveal_window() {
+        let game = game(3);
+
+        assert_eq!(game.check_reveal_open(99), Err(ErrorCode::CommitPhaseActive.into()));
+        assert!(game.check_reveal_open(100).is_ok());
+        assert!(game.check_reveal_open(199).is_ok());
+        assert_eq!(game.check_reveal_open(200), Err(ErrorCode::RevealPhaseOver.into()));
+    }
+
+    #[test]
+    fn reveal_must_open_the_commitment() {
+        let mut game = game(3);
+        let salt = [7; 32];
+        let voter = commit(&mut game, 1, 2, &salt);
+
+        assert!(game.check_reveal(1, &voter, 2, &salt).is_ok());
+        assert_eq!(
+            game.check_reveal(1, &voter, 2, &[8; 32]),
+            Err(ErrorCode::CommitmentMismatch.into())
+        );
+        assert_eq!(
+            game.check_reveal(1, &voter, 1, &salt),
+            Err(ErrorCode::CommitmentMismatch.into())
+        );
+        assert_eq!(
+            game.check_reveal(1, &Pubkey::new_unique(), 2, &salt),
+            Err(ErrorCode::InvalidAuthority.into())
+        );
+
+        game.votes[1] = 2;
+        assert_eq!(game.check_reveal(1, &voter, 2, &salt), Err(ErrorCode::AlreadyRevealed.into()));
+    }
+
+    #[test]
+    fn result_counts_only_revealed_ballots() {
+        let mut game = game(4);
+        let salt = [7; 32];
+        commit(&mut game, 0, 1, &salt);
+        commit(&mut game, 1, 2, &salt);
+        commit(&mut game, 2, 2, &salt);
+        game.votes[0] = 1;
+        game.votes[1] = 2;
+
+        // Seat 2 committed but did not reveal, seat 3 never committed
+        assert_eq!(game.tally(), (1, 1));
+        assert_eq!(game.check_result_ready(150), Err(ErrorCode::RevealPhaseActive.into()));
+        assert!(game.check_result_ready(200).is_ok());
+
+        game.votes[2] = 2;
+        assert_eq!(game.tally(), (1, 2));
+        assert!(game.check_result_ready(150).is_ok());
+        assert_eq!(game.check_result_ready(99), Err(ErrorCode::CommitPhaseActive.into()));
+    }
 }